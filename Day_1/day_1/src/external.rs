@@ -0,0 +1,284 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Both parts for two-column files of any size: each column is read in chunks
+// that fit the memory limit, sorted and spilled to a temporary file, then the
+// sorted runs are merged back. The distance pairs the merged columns in order
+// like part 1, and the similarity walks the two sorted columns side by side
+// counting equal values, so neither needs more than a read buffer per run.
+
+// Read buffer for every run taking part in a merge
+const RUN_BUFFER: usize = 64 * 1024;
+
+pub struct ExternalConfig {
+    // Rough ceiling on the memory used for buffered values and run readers
+    pub memory_limit: usize,
+    pub temp_dir: PathBuf,
+}
+
+impl Default for ExternalConfig {
+    fn default() -> Self {
+        ExternalConfig {
+            memory_limit: 64 * 1024 * 1024,
+            temp_dir: env::temp_dir(),
+        }
+    }
+}
+
+// A sorted run on disk, deleted when dropped
+struct Run {
+    path: PathBuf,
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+static NEXT_RUN: AtomicUsize = AtomicUsize::new(0);
+
+fn new_run_path(dir: &Path) -> PathBuf {
+    let n = NEXT_RUN.fetch_add(1, Ordering::SeqCst);
+    dir.join(format!("day_1-run-{}-{}.bin", process::id(), n))
+}
+
+fn write_run(dir: &Path, values: &mut Vec<i64>) -> io::Result<Run> {
+    values.sort_unstable();
+    let run = Run { path: new_run_path(dir) };
+    let mut writer = BufWriter::new(File::create(&run.path)?);
+    for value in values.iter() {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.flush()?;
+    values.clear();
+    Ok(run)
+}
+
+struct RunReader {
+    reader: BufReader<File>,
+}
+
+impl RunReader {
+    fn open(run: &Run) -> io::Result<Self> {
+        Ok(RunReader {
+            reader: BufReader::with_capacity(RUN_BUFFER, File::open(&run.path)?),
+        })
+    }
+
+    fn next_value(&mut self) -> io::Result<Option<i64>> {
+        let mut bytes = [0; 8];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => Ok(Some(i64::from_le_bytes(bytes))),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// Yields the values of several sorted runs in order
+struct Merge {
+    readers: Vec<RunReader>,
+    heap: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl Merge {
+    fn new(runs: &[Run]) -> io::Result<Self> {
+        let mut readers = runs.iter().map(RunReader::open).collect::<io::Result<Vec<_>>>()?;
+        let mut heap = BinaryHeap::new();
+        for (i, reader) in readers.iter_mut().enumerate() {
+            if let Some(value) = reader.next_value()? {
+                heap.push(Reverse((value, i)));
+            }
+        }
+        Ok(Merge { readers, heap })
+    }
+
+    fn next_value(&mut self) -> io::Result<Option<i64>> {
+        let Some(Reverse((value, i))) = self.heap.pop() else {
+            return Ok(None);
+        };
+        if let Some(next) = self.readers[i].next_value()? {
+            self.heap.push(Reverse((next, i)));
+        }
+        Ok(Some(value))
+    }
+
+    // The next value and how many times it repeats
+    fn next_group(&mut self) -> io::Result<Option<(i64, i64)>> {
+        let Some(value) = self.next_value()? else {
+            return Ok(None);
+        };
+        let mut count = 1;
+        while let Some(&Reverse((next, _))) = self.heap.peek() {
+            if next != value {
+                break;
+            }
+            self.next_value()?;
+            count += 1;
+        }
+        Ok(Some((value, count)))
+    }
+}
+
+// How many runs can be merged at once without their buffers passing the limit
+fn fan_in(config: &ExternalConfig) -> usize {
+    // Both columns are merged side by side
+    (config.memory_limit / (2 * RUN_BUFFER)).max(2)
+}
+
+// Merges runs in batches until few enough remain to merge in one go
+fn reduce_runs(mut runs: Vec<Run>, config: &ExternalConfig) -> io::Result<Vec<Run>> {
+    let fan_in = fan_in(config);
+    while runs.len() > fan_in {
+        let mut merged = Vec::new();
+        for batch in runs.chunks(fan_in) {
+            let mut merge = Merge::new(batch)?;
+            let run = Run { path: new_run_path(&config.temp_dir) };
+            let mut writer = BufWriter::new(File::create(&run.path)?);
+            while let Some(value) = merge.next_value()? {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            writer.flush()?;
+            merged.push(run);
+        }
+        runs = merged;
+    }
+    Ok(runs)
+}
+
+fn spill_columns(path: &Path, config: &ExternalConfig) -> io::Result<(Vec<Run>, Vec<Run>)> {
+    // Half the limit for each column's buffer
+    let capacity = (config.memory_limit / 2 / size_of::<i64>()).max(1);
+    let mut left = Vec::with_capacity(capacity);
+    let mut right = Vec::with_capacity(capacity);
+    let (mut left_runs, mut right_runs) = (Vec::new(), Vec::new());
+
+    let parse = |s: &str| {
+        s.parse::<i64>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", s, e)))
+    };
+
+    let reader = BufReader::new(File::open(path)?);
+    for line in reader.lines() {
+        let line = line?;
+        let mut iter = line.split_whitespace();
+        if let (Some(l), Some(r)) = (iter.next(), iter.next()) {
+            left.push(parse(l)?);
+            right.push(parse(r)?);
+        }
+
+        if left.len() == capacity {
+            left_runs.push(write_run(&config.temp_dir, &mut left)?);
+            right_runs.push(write_run(&config.temp_dir, &mut right)?);
+        }
+    }
+    if !left.is_empty() {
+        left_runs.push(write_run(&config.temp_dir, &mut left)?);
+        right_runs.push(write_run(&config.temp_dir, &mut right)?);
+    }
+
+    Ok((reduce_runs(left_runs, config)?, reduce_runs(right_runs, config)?))
+}
+
+fn distance(left: &[Run], right: &[Run]) -> io::Result<i64> {
+    let (mut left, mut right) = (Merge::new(left)?, Merge::new(right)?);
+    let mut total = 0;
+    while let (Some(l), Some(r)) = (left.next_value()?, right.next_value()?) {
+        total += (l - r).abs();
+    }
+    Ok(total)
+}
+
+fn similarity(left: &[Run], right: &[Run]) -> io::Result<i64> {
+    let (mut left, mut right) = (Merge::new(left)?, Merge::new(right)?);
+    let (mut l, mut r) = (left.next_group()?, right.next_group()?);
+    let mut total = 0;
+
+    while let (Some((lv, lc)), Some((rv, rc))) = (l, r) {
+        if lv < rv {
+            l = left.next_group()?;
+        } else if rv < lv {
+            r = right.next_group()?;
+        } else {
+            total += lv * lc * rc;
+            l = left.next_group()?;
+            r = right.next_group()?;
+        }
+    }
+    Ok(total)
+}
+
+// (part 1, part 2) for a file too large to hold in memory
+pub fn solve_file(path: &Path, config: &ExternalConfig) -> io::Result<(i64, i64)> {
+    let (left, right) = spill_columns(path, config)?;
+    Ok((distance(&left, &right)?, similarity(&left, &right)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A scratch directory for the input and the runs, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("day_1_external_{}_{}", process::id(), name));
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    // Solves `input` under each memory limit and checks it against part_1
+    // and part_2, and that every run was cleaned up afterwards
+    fn check(name: &str, input: &str) {
+        let dir = TempDir::new(name);
+        let path = dir.0.join("input.txt");
+        fs::write(&path, input).unwrap();
+        let runs = dir.0.join("runs");
+        fs::create_dir_all(&runs).unwrap();
+
+        let expected = (crate::part_1(input) as i64, crate::part_2(input));
+        // 1 byte still buffers one value a column; 48 bytes buffer three
+        for memory_limit in [1, 16, 48, 1024] {
+            let config = ExternalConfig {
+                memory_limit,
+                temp_dir: runs.clone(),
+            };
+            assert_eq!(solve_file(&path, &config).unwrap(), expected, "{} with {} bytes", name, memory_limit);
+            assert_eq!(fs::read_dir(&runs).unwrap().count(), 0, "{} left runs behind", name);
+        }
+    }
+
+    #[test]
+    fn matches_in_memory_parts() {
+        check("sample", "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+        check("empty", "");
+        check("one", "7   7\n");
+
+        // Enough lines for several rounds of merging at two runs at a time,
+        // with plenty of repeats for the similarity
+        let mut state: u64 = 0x0001_0037;
+        let mut next = |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % n
+        };
+        let input: String = (0..300).map(|_| format!("{}   {}\n", next(40), next(40))).collect();
+        check("random", &input);
+    }
+}
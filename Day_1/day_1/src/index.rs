@@ -0,0 +1,549 @@
+use std::collections::HashMap;
+
+// Both location-ID lists kept live, with the part 1 distance and part 2
+// similarity updated on every insert and removal instead of recomputed.
+//
+// Distance: pairing the sorted lists is the same as summing, over every
+// distinct value u, |(left values <= u) - (right values <= u)| times the gap
+// from u to the next distinct value. Inserting v on the left adds 1 to that
+// difference at every u >= v, which changes the total by +gap where the
+// difference was >= 0 and -gap where it was < 0.
+//
+// That share depends on how the differences past v split around zero, which
+// a Fenwick or segment tree can't summarise per node: shifting a range by one
+// moves every difference sitting at exactly -1 or 0 across, and those can be
+// anywhere. So the distinct values are kept in order in blocks of about B.
+// Each block has an offset added to all its differences and keeps its
+// differences in sorted order with running gap totals, so a whole block takes
+// an update by bumping the offset and its share of the change is one binary
+// search. Only the block holding v is touched value by value, and its order
+// is patched by merging rather than re-sorted. An update costs O(n / B + B)
+// for n distinct values, O(sqrt n) with B = 256 up to a few hundred thousand
+// values. Blocks are split past 2B values and merged with a neighbour below
+// B / 2, so they stay within a constant factor of B however the values come
+// and go, and memory follows what's stored.
+//
+// Similarity: sum of value * left count * right count, which changes by
+// value * (count on the other side) per insert or removal.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+// Distinct values per block; a block is split once it's twice this and
+// merged into a neighbour once it's under half
+const BLOCK: usize = 256;
+
+struct Block {
+    values: Vec<i32>,
+    // Difference at each value, less `offset`
+    diffs: Vec<i64>,
+    // From each value to the next distinct value, 0 for the largest
+    gaps: Vec<i64>,
+    offset: i64,
+    // Indices in order of difference, those differences, and running totals
+    // of their gaps (one longer)
+    order: Vec<usize>,
+    sorted: Vec<i64>,
+    running: Vec<i64>,
+}
+
+impl Block {
+    fn new(values: Vec<i32>, diffs: Vec<i64>, gaps: Vec<i64>, offset: i64) -> Self {
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_unstable_by_key(|&i| diffs[i]);
+        let mut block = Block {
+            values,
+            diffs,
+            gaps,
+            offset,
+            order,
+            sorted: Vec::new(),
+            running: Vec::new(),
+        };
+        block.total_gaps();
+        block
+    }
+
+    // Recomputes `sorted` and `running` from `order`
+    fn total_gaps(&mut self) {
+        self.sorted.clear();
+        self.sorted.extend(self.order.iter().map(|&j| self.diffs[j]));
+        self.running.clear();
+        self.running.push(0);
+        for k in 0..self.order.len() {
+            self.running.push(self.running[k] + self.gaps[self.order[k]]);
+        }
+    }
+
+    // Where a difference (less offset) goes in `order`
+    fn rank(&self, diff: i64) -> usize {
+        self.sorted.partition_point(|&d| d < diff)
+    }
+
+    // Total gap over values whose difference is at least `at`
+    fn gap_from(&self, at: i64) -> i64 {
+        let k = self.rank(at - self.offset);
+        self.running[self.order.len()] - self.running[k]
+    }
+
+    // Change in the block's share of the distance if every difference moves by delta (±1)
+    fn change(&self, delta: i64) -> i64 {
+        let all = self.running[self.order.len()];
+        if delta > 0 {
+            2 * self.gap_from(0) - all
+        } else {
+            all - 2 * self.gap_from(1)
+        }
+    }
+
+    fn diff(&self, i: usize) -> i64 {
+        self.diffs[i] + self.offset
+    }
+
+    fn set_gap(&mut self, i: usize, gap: i64) {
+        let change = gap - self.gaps[i];
+        self.gaps[i] = gap;
+        let k = self.order.iter().position(|&j| j == i).unwrap();
+        for total in &mut self.running[k + 1..] {
+            *total += change;
+        }
+    }
+
+    fn insert(&mut self, i: usize, value: i32, diff: i64, gap: i64) {
+        for j in &mut self.order {
+            *j += (*j >= i) as usize;
+        }
+        self.values.insert(i, value);
+        self.diffs.insert(i, diff - self.offset);
+        self.gaps.insert(i, gap);
+        let k = self.rank(diff - self.offset);
+        self.order.insert(k, i);
+        self.total_gaps();
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.values.remove(i);
+        self.diffs.remove(i);
+        self.gaps.remove(i);
+        self.order.retain(|&j| j != i);
+        for j in &mut self.order {
+            *j -= (*j > i) as usize;
+        }
+        self.total_gaps();
+    }
+
+    // Adds delta (±1) to the differences from index `from` on. Both the
+    // shifted and the unshifted ones stay in order among themselves, so the
+    // two runs are merged back together.
+    fn add_from(&mut self, from: usize, delta: i64) {
+        for diff in &mut self.diffs[from..] {
+            *diff += delta;
+        }
+        let (shifted, kept): (Vec<usize>, Vec<usize>) = self.order.iter().partition(|&&j| j >= from);
+        self.order.clear();
+        let (mut a, mut b) = (kept.into_iter().peekable(), shifted.into_iter().peekable());
+        while let (Some(&x), Some(&y)) = (a.peek(), b.peek()) {
+            if self.diffs[x] <= self.diffs[y] {
+                self.order.push(x);
+                a.next();
+            } else {
+                self.order.push(y);
+                b.next();
+            }
+        }
+        self.order.extend(a.chain(b));
+        self.total_gaps();
+    }
+
+    // Into its parts, with the offset folded into the differences
+    fn into_parts(self) -> (Vec<i32>, Vec<i64>, Vec<i64>) {
+        let offset = self.offset;
+        (self.values, self.diffs.into_iter().map(|d| d + offset).collect(), self.gaps)
+    }
+}
+
+struct Diffs {
+    // In value order, none empty
+    blocks: Vec<Block>,
+    total: i64,
+}
+
+impl Diffs {
+    fn new() -> Self {
+        Diffs {
+            blocks: Vec::new(),
+            total: 0,
+        }
+    }
+
+    // Builds from every distinct value, sorted, with the difference at it
+    fn build(values: Vec<i32>, diffs: Vec<i64>) -> Self {
+        let gaps: Vec<i64> = (0..values.len())
+            .map(|i| values.get(i + 1).map_or(0, |&next| next as i64 - values[i] as i64))
+            .collect();
+        let total = diffs.iter().zip(&gaps).map(|(d, g)| d.abs() * g).sum();
+        let blocks = (0..values.len())
+            .step_by(BLOCK)
+            .map(|at| {
+                let to = (at + BLOCK).min(values.len());
+                Block::new(values[at..to].to_vec(), diffs[at..to].to_vec(), gaps[at..to].to_vec(), 0)
+            })
+            .collect();
+        Diffs { blocks, total }
+    }
+
+    // (block, index) where `value` is or would go
+    fn locate(&self, value: i32) -> (usize, usize) {
+        let b = self.blocks.partition_point(|block| block.values[0] <= value).saturating_sub(1);
+        (b, self.blocks[b].values.partition_point(|&v| v < value))
+    }
+
+    fn previous(&self, b: usize, i: usize) -> Option<(usize, usize)> {
+        match (b, i) {
+            (0, 0) => None,
+            (_, 0) => Some((b - 1, self.blocks[b - 1].values.len() - 1)),
+            _ => Some((b, i - 1)),
+        }
+    }
+
+    fn following(&self, b: usize, i: usize) -> Option<(usize, usize)> {
+        if i + 1 < self.blocks[b].values.len() {
+            Some((b, i + 1))
+        } else {
+            (b + 1 < self.blocks.len()).then_some((b + 1, 0))
+        }
+    }
+
+    fn set_gap(&mut self, (b, i): (usize, usize), gap: i64) {
+        let block = &mut self.blocks[b];
+        self.total += block.diff(i).abs() * (gap - block.gaps[i]);
+        block.set_gap(i, gap);
+    }
+
+    // Splits block b if it has grown past 2B, or merges it with a neighbour
+    // if it has shrunk under B / 2, splitting the result again if needed
+    fn rebalance(&mut self, b: usize) {
+        let len = self.blocks[b].values.len();
+        if len > 2 * BLOCK {
+            let (values, diffs, gaps) = self.blocks.remove(b).into_parts();
+            self.insert_halves(b, values, diffs, gaps);
+        } else if len < BLOCK / 2 && self.blocks.len() > 1 {
+            let b = if b + 1 < self.blocks.len() { b } else { b - 1 };
+            let (mut values, mut diffs, mut gaps) = self.blocks.remove(b).into_parts();
+            let (more_values, more_diffs, more_gaps) = self.blocks.remove(b).into_parts();
+            values.extend(more_values);
+            diffs.extend(more_diffs);
+            gaps.extend(more_gaps);
+            if values.len() > 2 * BLOCK {
+                self.insert_halves(b, values, diffs, gaps);
+            } else {
+                self.blocks.insert(b, Block::new(values, diffs, gaps, 0));
+            }
+        }
+    }
+
+    fn insert_halves(&mut self, b: usize, mut values: Vec<i32>, mut diffs: Vec<i64>, mut gaps: Vec<i64>) {
+        let half = values.len() / 2;
+        let rest = Block::new(values.split_off(half), diffs.split_off(half), gaps.split_off(half), 0);
+        self.blocks.insert(b, Block::new(values, diffs, gaps, 0));
+        self.blocks.insert(b + 1, rest);
+    }
+
+    // Adds a value not yet present, with the difference of the value before it
+    fn insert_value(&mut self, value: i32) {
+        if self.blocks.is_empty() {
+            self.blocks.push(Block::new(vec![value], vec![0], vec![0], 0));
+            return;
+        }
+
+        let (b, i) = self.locate(value);
+        let previous = self.previous(b, i);
+        let next = match i < self.blocks[b].values.len() {
+            true => Some((b, i)),
+            false => (b + 1 < self.blocks.len()).then_some((b + 1, 0)),
+        };
+        let diff = previous.map_or(0, |(pb, pi)| self.blocks[pb].diff(pi));
+        let gap = next.map_or(0, |(nb, ni)| self.blocks[nb].values[ni] as i64 - value as i64);
+        if let Some((pb, pi)) = previous {
+            let gap = value as i64 - self.blocks[pb].values[pi] as i64;
+            self.set_gap((pb, pi), gap);
+        }
+
+        self.total += diff.abs() * gap;
+        self.blocks[b].insert(i, value, diff, gap);
+        self.rebalance(b);
+    }
+
+    // Drops a present value, whose difference should by now equal the one
+    // before it (or be 0 with nothing before it)
+    fn remove_value(&mut self, value: i32) {
+        let (b, i) = self.locate(value);
+        self.total -= self.blocks[b].diff(i).abs() * self.blocks[b].gaps[i];
+        let previous = self.previous(b, i);
+        let next = self.following(b, i);
+        if let Some((pb, pi)) = previous {
+            let gap = next.map_or(0, |(nb, ni)| self.blocks[nb].values[ni] as i64 - self.blocks[pb].values[pi] as i64);
+            self.set_gap((pb, pi), gap);
+        }
+
+        self.blocks[b].remove(i);
+        if self.blocks[b].values.is_empty() {
+            self.blocks.remove(b);
+        } else {
+            self.rebalance(b);
+        }
+    }
+
+    // Adds delta (±1) to the difference at every present value >= from
+    fn add_from(&mut self, from: i32, delta: i64) {
+        let (b, i) = self.locate(from);
+        let block = &mut self.blocks[b];
+        for j in i..block.values.len() {
+            let diff = block.diff(j);
+            self.total += ((diff + delta).abs() - diff.abs()) * block.gaps[j];
+        }
+        block.add_from(i, delta);
+
+        for block in &mut self.blocks[b + 1..] {
+            self.total += block.change(delta);
+            block.offset += delta;
+        }
+    }
+}
+
+pub struct ListIndex {
+    diffs: Diffs,
+    // value -> (left count, right count)
+    counts: HashMap<i32, (i64, i64)>,
+    left_len: usize,
+    right_len: usize,
+    similarity: i64,
+}
+
+impl Default for ListIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ListIndex {
+    pub fn new() -> Self {
+        ListIndex {
+            diffs: Diffs::new(),
+            counts: HashMap::new(),
+            left_len: 0,
+            right_len: 0,
+            similarity: 0,
+        }
+    }
+
+    // Bulk load, much faster than inserting the values one by one
+    pub fn from_lists(left: &[i32], right: &[i32]) -> Self {
+        let mut counts: HashMap<i32, (i64, i64)> = HashMap::new();
+        for &value in left {
+            counts.entry(value).or_insert((0, 0)).0 += 1;
+        }
+        for &value in right {
+            counts.entry(value).or_insert((0, 0)).1 += 1;
+        }
+
+        let mut values: Vec<i32> = counts.keys().copied().collect();
+        values.sort_unstable();
+        let diffs = values
+            .iter()
+            .scan(0, |diff, value| {
+                let (l, r) = counts[value];
+                *diff += l - r;
+                Some(*diff)
+            })
+            .collect();
+
+        let similarity = counts.iter().map(|(&value, &(l, r))| value as i64 * l * r).sum();
+
+        ListIndex {
+            diffs: Diffs::build(values, diffs),
+            counts,
+            left_len: left.len(),
+            right_len: right.len(),
+            similarity,
+        }
+    }
+
+    // Loads the puzzle's two-column format
+    pub fn from_input(input: &str) -> Self {
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        for line in input.lines() {
+            let mut iter = line.split_whitespace();
+            if let (Some(l), Some(r)) = (iter.next(), iter.next()) {
+                left.push(l.parse().unwrap());
+                right.push(r.parse().unwrap());
+            }
+        }
+        ListIndex::from_lists(&left, &right)
+    }
+
+    pub fn insert(&mut self, side: Side, value: i32) {
+        self.update(side, value, 1);
+    }
+
+    // Returns false, changing nothing, if the value isn't in that list
+    pub fn remove(&mut self, side: Side, value: i32) -> bool {
+        if self.count(side, value) == 0 {
+            return false;
+        }
+        self.update(side, value, -1);
+        true
+    }
+
+    fn update(&mut self, side: Side, value: i32, delta: i64) {
+        if !self.counts.contains_key(&value) {
+            self.diffs.insert_value(value);
+        }
+        let diff_delta = if side == Side::Left { delta } else { -delta };
+        self.diffs.add_from(value, diff_delta);
+
+        let counts = self.counts.entry(value).or_insert((0, 0));
+        let other = match side {
+            Side::Left => {
+                counts.0 += delta;
+                self.left_len = (self.left_len as i64 + delta) as usize;
+                counts.1
+            }
+            Side::Right => {
+                counts.1 += delta;
+                self.right_len = (self.right_len as i64 + delta) as usize;
+                counts.0
+            }
+        };
+        if *counts == (0, 0) {
+            self.counts.remove(&value);
+            self.diffs.remove_value(value);
+        }
+
+        self.similarity += value as i64 * other * delta;
+    }
+
+    pub fn count(&self, side: Side, value: i32) -> i64 {
+        let (left, right) = self.counts.get(&value).copied().unwrap_or((0, 0));
+        match side {
+            Side::Left => left,
+            Side::Right => right,
+        }
+    }
+
+    pub fn len(&self, side: Side) -> usize {
+        match side {
+            Side::Left => self.left_len,
+            Side::Right => self.right_len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left_len == 0 && self.right_len == 0
+    }
+
+    // Part 1's total; only defined while both lists are the same length
+    pub fn distance(&self) -> Option<i64> {
+        (self.left_len == self.right_len).then_some(self.diffs.total)
+    }
+
+    // Part 2's score
+    pub fn similarity(&self) -> i64 {
+        self.similarity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Small xorshift so the updates are the same every run
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    fn brute_distance(left: &[i32], right: &[i32]) -> i64 {
+        let (mut left, mut right) = (left.to_vec(), right.to_vec());
+        left.sort_unstable();
+        right.sort_unstable();
+        left.iter().zip(&right).map(|(&l, &r)| (l as i64 - r as i64).abs()).sum()
+    }
+
+    fn brute_similarity(left: &[i32], right: &[i32]) -> i64 {
+        let mut right_counts: HashMap<i32, i64> = HashMap::new();
+        for &r in right {
+            *right_counts.entry(r).or_insert(0) += 1;
+        }
+        left.iter().map(|&l| l as i64 * right_counts.get(&l).unwrap_or(&0)).sum()
+    }
+
+    fn check(index: &ListIndex, left: &[i32], right: &[i32]) {
+        let distance = (left.len() == right.len()).then(|| brute_distance(left, right));
+        assert_eq!(index.distance(), distance);
+        assert_eq!(index.similarity(), brute_similarity(left, right));
+        assert_eq!((index.len(Side::Left), index.len(Side::Right)), (left.len(), right.len()));
+    }
+
+    // Grows both lists past several blocks, shrinks them back down so the
+    // blocks merge, then grows them again, checking after every update
+    fn run(mut rng: Rng, spread: u64, start: &[i32]) {
+        let (mut left, mut right) = (start.to_vec(), start.to_vec());
+        let mut index = ListIndex::from_lists(&left, &right);
+        check(&index, &left, &right);
+        for target in [1200, 40, 900, 0] {
+            while left.len() + right.len() != target {
+                let grow = left.len() + right.len() < target;
+                let side = if rng.below(2) == 0 { Side::Left } else { Side::Right };
+                let list = match side {
+                    Side::Left => &mut left,
+                    Side::Right => &mut right,
+                };
+                if grow {
+                    let value = rng.below(spread) as i32 - spread as i32 / 2;
+                    index.insert(side, value);
+                    list.push(value);
+                } else if !list.is_empty() {
+                    let value = list.swap_remove(rng.below(list.len() as u64) as usize);
+                    assert!(index.remove(side, value));
+                } else {
+                    assert!(!index.remove(side, 0));
+                    continue;
+                }
+                check(&index, &left, &right);
+            }
+        }
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn matches_sort_and_pair() {
+        // Mostly distinct values, so there are several blocks
+        run(Rng(0x0001_2024), 1_000_000, &[]);
+        // Lots of repeats, so counts go up and down without values leaving
+        run(Rng(0x0002_2024), 60, &[]);
+        // Starting from a bulk load
+        run(Rng(0x0003_2024), 5000, &(0..700).map(|i| i * 7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn extremes_dont_overflow() {
+        let mut index = ListIndex::new();
+        index.insert(Side::Left, i32::MIN);
+        index.insert(Side::Right, i32::MAX);
+        assert_eq!(index.distance(), Some(u32::MAX as i64));
+        assert!(!index.remove(Side::Left, i32::MAX));
+        assert!(index.remove(Side::Right, i32::MAX));
+        index.insert(Side::Right, i32::MIN);
+        assert_eq!((index.distance(), index.similarity()), (Some(0), i32::MIN as i64));
+    }
+}
@@ -0,0 +1,83 @@
+pub mod external;
+pub mod index;
+pub mod multi;
+pub mod report;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+
+// Counts of each number in the left and right columns
+pub fn counts(input: &str) -> (HashMap<i64, i64>, HashMap<i64, i64>) {
+    let mut left_map: HashMap<i64, i64> = HashMap::new();
+    let mut right_map: HashMap<i64, i64> = HashMap::new();
+
+    for line in input.lines() {
+        let nums: Vec<i64> = line
+            .split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect();
+
+        if nums.len() != 2 {
+            continue;
+        }
+
+        let (left_num, right_num) = (nums[0], nums[1]);
+
+        *left_map.entry(left_num).or_insert(0) += 1;
+        *right_map.entry(right_num).or_insert(0) += 1;
+    }
+
+    (left_map, right_map)
+}
+
+pub fn part_2(input: &str) -> i64 {
+    // left_map: how often each number appears in the left column
+    // right_map: how often each number appears in the right column
+    let (left_map, right_map) = counts(input);
+
+    let mut net_sim_score = 0;
+
+    for (key, value) in left_map.into_iter() {
+        let r_value = right_map.get(&key).unwrap_or(&0);
+        let sim_score = (key * r_value) * value;
+
+        net_sim_score += sim_score
+    }
+
+    net_sim_score
+}
+
+// The two columns paired smallest to smallest, popped off min-heaps as needed
+pub fn pairs(input: &str) -> impl Iterator<Item = (i32, i32)> {
+    // Create two min-heaps using Reverse for min-heap behavior
+    let mut heap1: BinaryHeap<Reverse<i32>> = BinaryHeap::new();
+    let mut heap2: BinaryHeap<Reverse<i32>> = BinaryHeap::new();
+
+    // Parse the input
+    for line in input.lines() {
+        let mut iter = line.split_whitespace();
+        if let (Some(num1), Some(num2)) = (iter.next(), iter.next()) {
+            let num1: i32 = num1.parse().unwrap();
+            let num2: i32 = num2.parse().unwrap();
+            heap1.push(Reverse(num1));
+            heap2.push(Reverse(num2));
+        }
+    }
+
+    // Stops once either heap is empty
+    std::iter::from_fn(move || match (heap1.pop(), heap2.pop()) {
+        (Some(Reverse(num1)), Some(Reverse(num2))) => Some((num1, num2)),
+        _ => None,
+    })
+}
+
+pub fn part_1(input: &str) -> i32 {
+    let mut net_distance = 0;
+
+    // Process all numbers
+    for (num1, num2) in pairs(input) {
+        net_distance += (num1 - num2).abs();
+    }
+    net_distance
+}
@@ -1,82 +1,90 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead};
-
-fn part_2(filename: &str) {
-    let file = File::open(filename).unwrap();
-    let reader = io::BufReader::new(file);
-
-    // left_map: stores numbers from left column and their frequency in right column
-    // right_map: stores numbers seen in right column but not yet in left column
-    let mut left_map: HashMap<i64, i64> = HashMap::new();
-    let mut right_map: HashMap<i64, i64> = HashMap::new();
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let nums: Vec<i64> = line
-            .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect();
-
-        if nums.len() != 2 {
-            continue;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use day_1::external::{self, ExternalConfig};
+use day_1::multi::{self, Lists};
+use day_1::report::Report;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let input_file = args
+        .first()
+        .filter(|a| !a.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "../../input/day1/sample.txt".to_string());
+
+    // --external streams the file through sorted runs on disk instead of
+    // reading it whole, keeping to --memory MiB (64 by default)
+    if args.iter().any(|a| a == "--external") {
+        let mut config = ExternalConfig::default();
+        if let Some(i) = args.iter().position(|a| a == "--memory") {
+            let mib: usize = args.get(i + 1).and_then(|m| m.parse().ok()).expect("--memory takes a size in MiB");
+            config.memory_limit = mib * 1024 * 1024;
         }
-
-        let (left_num, right_num) = (nums[0], nums[1]);
-
-        *left_map.entry(left_num).or_insert(0) += 1;
-        *right_map.entry(right_num).or_insert(0) += 1;
+        let (distance, similarity) = external::solve_file(Path::new(&input_file), &config).expect("Failed to solve input file");
+        println!("Part 1: {}", distance);
+        println!("Part 2: {}", similarity);
+        return;
     }
 
-    let mut net_sim_score = 0;
-
-    for (key, value) in left_map.into_iter() {
-        let r_value = right_map.get(&key).unwrap_or(&0);
-        let sim_score = (key * r_value) * value;
-
-        net_sim_score += sim_score
-    }
-
-    println!("Part 2: {}", net_sim_score);
-}
-
-fn part_1(filename: &str) {
-    let file = File::open(filename).unwrap();
-    let reader = io::BufReader::new(file);
-
-    // Create two min-heaps using Reverse for min-heap behavior
-    let mut heap1: BinaryHeap<Reverse<i32>> = BinaryHeap::new();
-    let mut heap2: BinaryHeap<Reverse<i32>> = BinaryHeap::new();
-
-    // Read and parse the input file
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let mut iter = line.split_whitespace();
-        if let (Some(num1), Some(num2)) = (iter.next(), iter.next()) {
-            let num1: i32 = num1.parse().unwrap();
-            let num2: i32 = num2.parse().unwrap();
-            heap1.push(Reverse(num1));
-            heap2.push(Reverse(num2));
+    let contents = fs::read_to_string(&input_file).expect("Failed to read input file");
+
+    // --report explains the totals: the --top <k> (10) largest pairs, a
+    // histogram with --buckets <n> (10) buckets, the shared values and every
+    // sorted pair. --csv <dir> writes each table to its own CSV file instead.
+    if args.iter().any(|a| a == "--report") {
+        let report = Report::new(&contents);
+        let top_k = flag_number(&args, "--top").unwrap_or(10);
+        let buckets = flag_number(&args, "--buckets").unwrap_or(10);
+
+        match args.iter().position(|a| a == "--csv") {
+            Some(i) => {
+                let dir = Path::new(args.get(i + 1).expect("--csv takes a directory"));
+                fs::create_dir_all(dir).expect("Failed to create CSV directory");
+                let files = [
+                    ("pairs.csv", report.pairs_csv()),
+                    ("top.csv", report.top_csv(top_k)),
+                    ("histogram.csv", report.histogram_csv(buckets)),
+                    ("shared.csv", report.shared_csv()),
+                ];
+                for (name, csv) in files {
+                    fs::write(dir.join(name), csv).expect("Failed to write CSV file");
+                    println!("Wrote {}", dir.join(name).display());
+                }
+            }
+            None => print!("{}", report.to_text(top_k, buckets)),
         }
+        return;
     }
 
-    let mut net_distance = 0;
-
-    // Process all numbers
-    while !heap1.is_empty() && !heap2.is_empty() {
-        if let (Some(Reverse(num1)), Some(Reverse(num2))) = (heap1.pop(), heap2.pop()) {
-            net_distance += (num1 - num2).abs();
+    // --lists takes any number of columns and compares every pair of them;
+    // --reference <n> shows only the similarity against list n
+    if args.iter().any(|a| a == "--lists") {
+        let lists = Lists::parse(&contents).unwrap_or_else(|e| panic!("Failed to parse input file: {}", e));
+        if let Some(i) = args.iter().position(|a| a == "--reference") {
+            let reference: usize = args
+                .get(i + 1)
+                .and_then(|n| n.parse().ok())
+                .filter(|&n| n >= 1 && n <= lists.count())
+                .expect("--reference takes a list number from 1 to the number of columns");
+            println!("Similarity to L{}:", reference);
+            for (other, score) in lists.similarity_to(reference - 1).into_iter().enumerate() {
+                println!("  L{}: {}", other + 1, score);
+            }
+        } else {
+            println!("Distance:\n{}", multi::format_matrix(&lists.distance_matrix()));
+            println!("Similarity:\n{}", multi::format_matrix(&lists.similarity_matrix()));
         }
+        return;
     }
-    println!("Part 1: {}", net_distance);
-}
 
-fn main() -> io::Result<()> {
-    let input_file = "../../input/day1/sample.txt";
+    println!("Part 1: {}", day_1::part_1(&contents));
+    println!("Part 2: {}", day_1::part_2(&contents));
+}
 
-    part_1(input_file);
-    part_2(input_file);
-    Ok(())
+fn flag_number(args: &[String], flag: &str) -> Option<usize> {
+    let i = args.iter().position(|a| a == flag)?;
+    let value = args.get(i + 1).and_then(|n| n.parse().ok());
+    Some(value.unwrap_or_else(|| panic!("{} takes a number", flag)))
 }
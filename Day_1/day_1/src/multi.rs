@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+
+// Any number of location-ID lists, one per column, compared pairwise with the
+// part 1 distance and the part 2 similarity. Every column is sorted once, so
+// the distance between two lists is a walk down both in step and the
+// similarity is a merge of the two that multiplies the counts of equal values.
+//
+// The similarity adds up value * (count in one list) * (count in the other),
+// so it comes out the same whichever list is the reference; choosing a
+// reference picks which row of the matrix to look at.
+
+pub struct Lists {
+    // Each column, sorted
+    columns: Vec<Vec<i64>>,
+}
+
+impl Lists {
+    // Every non-empty line must have the same number of columns as the first
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut columns: Vec<Vec<i64>> = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let nums = line
+                .split_whitespace()
+                .map(|n| n.parse::<i64>().map_err(|e| format!("line {}: {}: {}", i + 1, n, e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if nums.is_empty() {
+                continue;
+            }
+
+            if columns.is_empty() {
+                columns = vec![Vec::new(); nums.len()];
+            } else if nums.len() != columns.len() {
+                return Err(format!(
+                    "line {}: expected {} columns, found {}",
+                    i + 1,
+                    columns.len(),
+                    nums.len()
+                ));
+            }
+            for (column, num) in columns.iter_mut().zip(nums) {
+                column.push(num);
+            }
+        }
+
+        for column in columns.iter_mut() {
+            column.sort_unstable();
+        }
+        Ok(Lists { columns })
+    }
+
+    pub fn count(&self) -> usize {
+        self.columns.len()
+    }
+
+    // Part 1 between lists a and b
+    pub fn distance(&self, a: usize, b: usize) -> i64 {
+        let (a, b) = (&self.columns[a], &self.columns[b]);
+        a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+    }
+
+    // Part 2 between lists a and b
+    pub fn similarity(&self, a: usize, b: usize) -> i64 {
+        let (a, b) = (&self.columns[a], &self.columns[b]);
+        let (mut i, mut j) = (0, 0);
+        let mut total = 0;
+
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    let value = a[i];
+                    let start_i = i;
+                    while i < a.len() && a[i] == value {
+                        i += 1;
+                    }
+                    let start_j = j;
+                    while j < b.len() && b[j] == value {
+                        j += 1;
+                    }
+                    total += value * (i - start_i) as i64 * (j - start_j) as i64;
+                }
+            }
+        }
+        total
+    }
+
+    pub fn distance_matrix(&self) -> Vec<Vec<i64>> {
+        self.matrix(Self::distance)
+    }
+
+    pub fn similarity_matrix(&self) -> Vec<Vec<i64>> {
+        self.matrix(Self::similarity)
+    }
+
+    // Similarity of every list against one reference list
+    pub fn similarity_to(&self, reference: usize) -> Vec<i64> {
+        (0..self.count()).map(|other| self.similarity(reference, other)).collect()
+    }
+
+    fn matrix(&self, score: fn(&Self, usize, usize) -> i64) -> Vec<Vec<i64>> {
+        // Both scores are symmetric, so only the upper half is computed
+        let n = self.count();
+        let upper: Vec<Vec<i64>> = (0..n).map(|a| (a..n).map(|b| score(self, a, b)).collect()).collect();
+        (0..n)
+            .map(|a| (0..n).map(|b| if b >= a { upper[a][b - a] } else { upper[b][a - b] }).collect())
+            .collect()
+    }
+}
+
+// Lays a matrix out with numbered rows and columns
+pub fn format_matrix(matrix: &[Vec<i64>]) -> String {
+    let width = matrix
+        .iter()
+        .flatten()
+        .map(|v| v.to_string().len())
+        .chain([format!("L{}", matrix.len()).len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{:>w$}", "", w = width);
+    for i in 0..matrix.len() {
+        out.push_str(&format!(" {:>w$}", format!("L{}", i + 1), w = width));
+    }
+    out.push('\n');
+    for (i, row) in matrix.iter().enumerate() {
+        out.push_str(&format!("{:>w$}", format!("L{}", i + 1), w = width));
+        for value in row {
+            out.push_str(&format!(" {:>w$}", value, w = width));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Three lists, the puzzle example's two and a third:
+    //   sorted A: 1 2 3 3 3 4
+    //   sorted B: 3 3 3 4 5 9
+    //   sorted C: 1 2 3 3 7 7
+    const LISTS: &str = "3 4 3\n4 3 3\n2 5 1\n1 3 7\n3 9 7\n3 3 2\n";
+
+    // Two of the lists as a two-column puzzle input
+    fn pair(a: usize, b: usize) -> String {
+        LISTS
+            .lines()
+            .map(|line| {
+                let nums: Vec<&str> = line.split_whitespace().collect();
+                format!("{}   {}\n", nums[a], nums[b])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matrices_match_the_parts() {
+        let lists = Lists::parse(LISTS).unwrap();
+        assert_eq!(lists.count(), 3);
+
+        // A-C: 0 0 0 0 4 3; B-C: 2 1 0 1 2 2
+        assert_eq!(lists.distance_matrix(), [[0, 11, 7], [11, 0, 8], [7, 8, 0]]);
+        // A-C: 1 + 2 + 3*3*2; B-C: 3*3*2; A-A: 1 + 2 + 3*3*3 + 4
+        assert_eq!(lists.similarity_matrix(), [[34, 31, 21], [31, 45, 18], [21, 18, 43]]);
+
+        for a in 0..3 {
+            for b in 0..3 {
+                assert_eq!(lists.distance(a, b), crate::part_1(&pair(a, b)) as i64, "distance {} {}", a, b);
+                assert_eq!(lists.similarity(a, b), crate::part_2(&pair(a, b)), "similarity {} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn reference_picks_a_row() {
+        let lists = Lists::parse(LISTS).unwrap();
+        assert_eq!(lists.similarity_to(2), [21, 18, 43]);
+        assert_eq!(lists.similarity_to(1), [31, 45, 18]);
+        // Swapping the columns doesn't change a score
+        assert_eq!(crate::part_2(&pair(2, 0)), crate::part_2(&pair(0, 2)));
+    }
+
+    #[test]
+    fn rejects_ragged_lines() {
+        assert_eq!(Lists::parse("1 2 3\n\n4 5\n").err().unwrap(), "line 3: expected 3 columns, found 2");
+        assert!(Lists::parse("1 x\n").is_err());
+    }
+
+    #[test]
+    fn lays_out_the_matrix() {
+        let matrix = Lists::parse("1 1\n2 3\n").unwrap().similarity_matrix();
+        assert_eq!(format_matrix(&matrix), "   L1 L2\nL1  3  1\nL2  1  4\n");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{counts, pairs};
+
+// Where the two totals come from: every sorted pair with its distance, the
+// pairs that contribute most, how the distances are spread, and the values
+// found in both lists with what each adds to the similarity.
+
+pub struct Pair {
+    // 1-based position in the sorted lists
+    pub rank: usize,
+    pub left: i64,
+    pub right: i64,
+    pub distance: i64,
+}
+
+pub struct Shared {
+    pub value: i64,
+    pub left_count: i64,
+    pub right_count: i64,
+    pub contribution: i64,
+}
+
+pub struct Bucket {
+    // Distances from lo to hi inclusive
+    pub lo: i64,
+    pub hi: i64,
+    pub pairs: usize,
+    pub total: i64,
+}
+
+pub struct Report {
+    pub pairs: Vec<Pair>,
+    // Ordered by value
+    pub shared: Vec<Shared>,
+    pub total_distance: i64,
+    pub total_similarity: i64,
+}
+
+impl Report {
+    pub fn new(input: &str) -> Self {
+        let pairs: Vec<Pair> = pairs(input)
+            .enumerate()
+            .map(|(i, (left, right))| {
+                let (left, right) = (left as i64, right as i64);
+                Pair {
+                    rank: i + 1,
+                    left,
+                    right,
+                    distance: (left - right).abs(),
+                }
+            })
+            .collect();
+
+        let (left_map, right_map) = counts(input);
+        let mut shared: Vec<Shared> = left_map
+            .into_iter()
+            .filter_map(|(value, left_count)| {
+                let right_count = *right_map.get(&value)?;
+                Some(Shared {
+                    value,
+                    left_count,
+                    right_count,
+                    contribution: value * left_count * right_count,
+                })
+            })
+            .collect();
+        shared.sort_unstable_by_key(|s| s.value);
+
+        Report {
+            total_distance: pairs.iter().map(|p| p.distance).sum(),
+            total_similarity: shared.iter().map(|s| s.contribution).sum(),
+            pairs,
+            shared,
+        }
+    }
+
+    // The k pairs furthest apart, earlier pairs first on ties
+    pub fn top(&self, k: usize) -> Vec<&Pair> {
+        let mut top: Vec<&Pair> = self.pairs.iter().collect();
+        top.sort_by_key(|p| (std::cmp::Reverse(p.distance), p.rank));
+        top.truncate(k);
+        top
+    }
+
+    // Distances split into equal-width buckets from 0 to the largest distance
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let Some(max) = self.pairs.iter().map(|p| p.distance).max() else {
+            return Vec::new();
+        };
+        let buckets = buckets.max(1) as i64;
+        let width = (max + 1 + buckets - 1) / buckets;
+
+        let mut histogram: Vec<Bucket> = (0..buckets)
+            .map(|i| Bucket {
+                lo: i * width,
+                hi: (i + 1) * width - 1,
+                pairs: 0,
+                total: 0,
+            })
+            .filter(|b| b.lo <= max)
+            .collect();
+        for pair in &self.pairs {
+            let bucket = &mut histogram[(pair.distance / width) as usize];
+            bucket.pairs += 1;
+            bucket.total += pair.distance;
+        }
+        histogram
+    }
+
+    pub fn to_text(&self, top_k: usize, buckets: usize) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("Total distance: {}\n", self.total_distance));
+        out.push_str(&format!("Total similarity: {}\n", self.total_similarity));
+
+        out.push_str(&format!("\nTop {} pairs by distance:\n", top_k));
+        for pair in self.top(top_k) {
+            out.push_str(&format!(
+                "  #{:<6} {:>8} {:>8} {:>8} ({:.1}%)\n",
+                pair.rank,
+                pair.left,
+                pair.right,
+                pair.distance,
+                percent(pair.distance, self.total_distance)
+            ));
+        }
+
+        out.push_str("\nDistance histogram:\n");
+        let histogram = self.histogram(buckets);
+        let most = histogram.iter().map(|b| b.pairs).max().unwrap_or(0).max(1);
+        for bucket in &histogram {
+            out.push_str(&format!(
+                "  {:>8}-{:<8} {:>6} {}\n",
+                bucket.lo,
+                bucket.hi,
+                bucket.pairs,
+                "#".repeat((bucket.pairs * 40).div_ceil(most))
+            ));
+        }
+
+        out.push_str("\nValues in both lists:\n");
+        out.push_str(&format!("  {:>8} {:>6} {:>6} {:>12}\n", "value", "left", "right", "contribution"));
+        for shared in &self.shared {
+            out.push_str(&format!(
+                "  {:>8} {:>6} {:>6} {:>12} ({:.1}%)\n",
+                shared.value,
+                shared.left_count,
+                shared.right_count,
+                shared.contribution,
+                percent(shared.contribution, self.total_similarity)
+            ));
+        }
+
+        out.push_str("\nSorted pairs:\n");
+        out.push_str(&format!("  {:<7} {:>8} {:>8} {:>8}\n", "rank", "left", "right", "distance"));
+        for pair in &self.pairs {
+            out.push_str(&format!(
+                "  #{:<6} {:>8} {:>8} {:>8}\n",
+                pair.rank, pair.left, pair.right, pair.distance
+            ));
+        }
+        out
+    }
+
+    pub fn pairs_csv(&self) -> String {
+        let mut out = String::from("rank,left,right,distance\n");
+        for pair in &self.pairs {
+            out.push_str(&format!("{},{},{},{}\n", pair.rank, pair.left, pair.right, pair.distance));
+        }
+        out
+    }
+
+    pub fn top_csv(&self, top_k: usize) -> String {
+        let mut out = String::from("rank,left,right,distance,share\n");
+        for pair in self.top(top_k) {
+            out.push_str(&format!(
+                "{},{},{},{},{:.4}\n",
+                pair.rank,
+                pair.left,
+                pair.right,
+                pair.distance,
+                percent(pair.distance, self.total_distance) / 100.0
+            ));
+        }
+        out
+    }
+
+    pub fn histogram_csv(&self, buckets: usize) -> String {
+        let mut out = String::from("lo,hi,pairs,total\n");
+        for bucket in self.histogram(buckets) {
+            out.push_str(&format!("{},{},{},{}\n", bucket.lo, bucket.hi, bucket.pairs, bucket.total));
+        }
+        out
+    }
+
+    pub fn shared_csv(&self) -> String {
+        let mut out = String::from("value,left_count,right_count,contribution\n");
+        for shared in &self.shared {
+            out.push_str(&format!(
+                "{},{},{},{}\n",
+                shared.value, shared.left_count, shared.right_count, shared.contribution
+            ));
+        }
+        out
+    }
+}
+
+fn percent(part: i64, total: i64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The puzzle example. Sorted pairs: (1,3) (2,3) (3,3) (3,4) (3,5) (4,9),
+    // distances 2 1 0 1 2 5; in both lists: 3 three times on each side, 4 once
+    const SAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn tables_as_csv() {
+        let report = Report::new(SAMPLE);
+        assert_eq!(
+            report.pairs_csv(),
+            "rank,left,right,distance\n1,1,3,2\n2,2,3,1\n3,3,3,0\n4,3,4,1\n5,3,5,2\n6,4,9,5\n"
+        );
+        // Ties on distance keep the earlier pair first; shares are of 11
+        assert_eq!(
+            report.top_csv(3),
+            "rank,left,right,distance,share\n6,4,9,5,0.4545\n1,1,3,2,0.1818\n5,3,5,2,0.1818\n"
+        );
+        assert_eq!(report.shared_csv(), "value,left_count,right_count,contribution\n3,3,3,27\n4,1,1,4\n");
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let report = Report::new(SAMPLE);
+        // Distances 0..=5 in three buckets two wide
+        assert_eq!(report.histogram_csv(3), "lo,hi,pairs,total\n0,1,3,2\n2,3,2,4\n4,5,1,5\n");
+        // A fourth bucket would start past the largest distance, so it's left out
+        assert_eq!(report.histogram_csv(4), report.histogram_csv(3));
+        assert_eq!(report.histogram_csv(0), "lo,hi,pairs,total\n0,5,6,11\n");
+        assert!(Report::new("").histogram(5).is_empty());
+    }
+
+    #[test]
+    fn text_report() {
+        let expected = [
+            "Total distance: 11",
+            "Total similarity: 31",
+            "",
+            "Top 2 pairs by distance:",
+            "  #6             4        9        5 (45.5%)",
+            "  #1             1        3        2 (18.2%)",
+            "",
+            "Distance histogram:",
+            "         0-1             3 ########################################",
+            "         2-3             2 ###########################",
+            "         4-5             1 ##############",
+            "",
+            "Values in both lists:",
+            "     value   left  right contribution",
+            "         3      3      3           27 (87.1%)",
+            "         4      1      1            4 (12.9%)",
+            "",
+            "Sorted pairs:",
+            "  rank        left    right distance",
+            "  #1             1        3        2",
+            "  #2             2        3        1",
+            "  #3             3        3        0",
+            "  #4             3        4        1",
+            "  #5             3        5        2",
+            "  #6             4        9        5",
+            "",
+        ];
+        assert_eq!(Report::new(SAMPLE).to_text(2, 3), expected.join("\n"));
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+pub fn part_1(contents: &str) -> u64 {
+    count_stones(contents, 25)
+}
+
+pub fn part_2(contents: &str) -> u64 {
+    count_stones(contents, 75)
+}
+
+fn count_stones(contents: &str, blinks: u32) -> u64 {
+    let stones: Vec<u64> = contents
+        .split_whitespace()
+        .map(|s| s.parse().expect("Should be a valid number"))
+        .collect();
+
+    let mut cache: HashMap<(u64, u32), u64> = HashMap::new();
+    stones.iter()
+        .map(|&stone| how_many_eventually(stone, blinks, &mut cache))
+        .sum()
+}
+
+fn how_many_eventually(x: u64, iters: u32, cache: &mut HashMap<(u64, u32), u64>) -> u64 {
+    // Check cache first
+    if let Some(&result) = cache.get(&(x, iters)) {
+        return result;
+    }
+
+    // Base case
+    if iters == 0 {
+        return 1;
+    }
+
+    let result = if x == 0 {
+        how_many_eventually(1, iters - 1, cache)
+    } else {
+        let digits = x.to_string();
+        let n = digits.len();
+
+        if n.is_multiple_of(2) {
+            let (left_str, right_str) = digits.split_at(n / 2);
+            let left = left_str.parse::<u64>().unwrap();
+            let right = right_str.parse::<u64>().unwrap();
+
+            how_many_eventually(left, iters - 1, cache) +
+                how_many_eventually(right, iters - 1, cache)
+        } else {
+            how_many_eventually(x * 2024, iters - 1, cache)
+        }
+    };
+
+    // Store in cache before returning
+    cache.insert((x, iters), result);
+    result
+}
@@ -0,0 +1,123 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Point {
+    row: i32,
+    col: i32,
+}
+
+const DIRS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)]; // up, right, down, left
+
+fn parse_input(input: &str) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+fn find_prices(grid: &[Vec<char>]) -> (i32, i32) {
+    let rows = grid.len() as i32;
+    let cols = grid[0].len() as i32;
+    let mut seen = HashSet::new();
+    let mut total_price1 = 0;
+    let mut total_price2 = 0;
+
+    // Helper function to check if a point is valid
+    let is_valid = |p: Point| {
+        p.row >= 0 && p.row < rows && p.col >= 0 && p.col < cols
+    };
+
+    for r in 0..rows {
+        for c in 0..cols {
+            let start = Point { row: r, col: c };
+            if seen.contains(&start) {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+            queue.push_back(start);
+            let mut area = 0;
+            let mut perimeter = 0;
+            let mut perim_points: HashMap<(i32, i32), HashSet<Point>> = HashMap::new();
+
+            // Find all points in this region and count perimeter
+            while let Some(current) = queue.pop_front() {
+                if seen.contains(&current) {
+                    continue;
+                }
+
+                seen.insert(current);
+                area += 1;
+
+                // Check all neighbors
+                for &(dr, dc) in &DIRS {
+                    let next = Point {
+                        row: current.row + dr,
+                        col: current.col + dc,
+                    };
+
+                    if is_valid(next) && grid[next.row as usize][next.col as usize] == grid[r as usize][c as usize] {
+                        queue.push_back(next);
+                    } else {
+                        perimeter += 1;
+                        // Group perimeter points by direction
+                        perim_points
+                            .entry((dr, dc))
+                            .or_default()
+                            .insert(current);
+                    }
+                }
+            }
+
+            // Count distinct sides
+            let mut sides = 0;
+            for points in perim_points.values() {
+                let mut seen_perim = HashSet::new();
+
+                for &start_point in points {
+                    if seen_perim.contains(&start_point) {
+                        continue;
+                    }
+
+                    // Found a new side
+                    sides += 1;
+
+                    // Find all connected perimeter points in this direction
+                    let mut perim_queue = VecDeque::new();
+                    perim_queue.push_back(start_point);
+
+                    while let Some(current) = perim_queue.pop_front() {
+                        if seen_perim.contains(&current) {
+                            continue;
+                        }
+                        seen_perim.insert(current);
+
+                        // Check all neighbors
+                        for &(dr, dc) in &DIRS {
+                            let next = Point {
+                                row: current.row + dr,
+                                col: current.col + dc,
+                            };
+                            if points.contains(&next) {
+                                perim_queue.push_back(next);
+                            }
+                        }
+                    }
+                }
+            }
+
+            total_price1 += area * perimeter;
+            total_price2 += area * sides;
+        }
+    }
+
+    (total_price1, total_price2)
+}
+
+pub fn part_1(input: &str) -> i32 {
+    find_prices(&parse_input(input)).0
+}
+
+pub fn part_2(input: &str) -> i32 {
+    find_prices(&parse_input(input)).1
+}
@@ -0,0 +1,78 @@
+#[derive(Debug, Default)]
+struct Machine {
+    x1: f64,  // Button A X movement
+    y1: f64,  // Button A Y movement
+    x2: f64,  // Button B X movement
+    y2: f64,  // Button B Y movement
+    prize_x: f64,
+    prize_y: f64,
+}
+
+fn parse_input(input: &str) -> Vec<Machine> {
+    let mut machines = Vec::new();
+    let mut current_machine = Machine::default();
+
+    for line in input.lines() {
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("Button") {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let button = parts[1].trim_end_matches(':');
+            let x = parts[2][2..].trim_end_matches(',').parse::<f64>().unwrap();
+            let y = parts[3][2..].parse::<f64>().unwrap();
+
+            if button == "A" {
+                current_machine.x1 = x;
+                current_machine.y1 = y;
+            } else {
+                current_machine.x2 = x;
+                current_machine.y2 = y;
+            }
+        } else if line.starts_with("Prize") {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            current_machine.prize_x = parts[1][2..].trim_end_matches(',').parse::<f64>().unwrap();
+            current_machine.prize_y = parts[2][2..].parse::<f64>().unwrap();
+            machines.push(current_machine);
+            current_machine = Machine::default();
+        }
+    }
+
+    machines
+}
+
+fn solve_puzzle(input: &str, part: i32) -> i64 {
+    let machines = parse_input(input);
+    let mut total_tokens = 0;
+    let offset = if part == 2 { 10_000_000_000_000_f64 } else { 0_f64 };
+
+    for machine in machines {
+        let prize_x = machine.prize_x + offset;
+        let prize_y = machine.prize_y + offset;
+
+        // Solve system of equations using cross multiplication
+        // x1*a + x2*b = prize_x
+        // y1*a + y2*b = prize_y
+
+        let denominator = machine.x1 * machine.y2 - machine.y1 * machine.x2;
+
+        let a = (prize_x * machine.y2 - prize_y * machine.x2) / denominator;
+        let b = (prize_y * machine.x1 - prize_x * machine.y1) / denominator;
+
+        // Check if we have integer solutions
+        if a.fract() == 0.0 && b.fract() == 0.0 && a >= 0.0 && b >= 0.0 {
+            total_tokens += (3.0 * a + b) as i64;
+        }
+    }
+
+    total_tokens
+}
+
+pub fn part_1(input: &str) -> i64 {
+    solve_puzzle(input, 1)
+}
+
+pub fn part_2(input: &str) -> i64 {
+    solve_puzzle(input, 2)
+}
@@ -0,0 +1,142 @@
+pub const WIDTH: i32 = 101;
+pub const HEIGHT: i32 = 103;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    pub pos: Position,
+    pub vel: Position,
+}
+
+pub fn parse_input(input: &str) -> Vec<Robot> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let parts: Vec<&str> = line.split(' ').collect();
+            let pos_parts: Vec<&str> = parts[0][2..].split(',').collect();
+            let vel_parts: Vec<&str> = parts[1][2..].split(',').collect();
+
+            Robot {
+                pos: Position {
+                    x: pos_parts[0].parse().unwrap(),
+                    y: pos_parts[1].parse().unwrap(),
+                },
+                vel: Position {
+                    x: vel_parts[0].parse().unwrap(),
+                    y: vel_parts[1].parse().unwrap(),
+                },
+            }
+        })
+        .collect()
+}
+
+fn simulate_robots(robots: &[Robot], width: i32, height: i32, seconds: i32) -> Vec<Robot> {
+    robots.iter().map(|robot| {
+        let new_x = ((robot.pos.x + seconds * robot.vel.x) % width + width) % width;
+        let new_y = ((robot.pos.y + seconds * robot.vel.y) % height + height) % height;
+        Robot {
+            pos: Position { x: new_x, y: new_y },
+            vel: robot.vel,
+        }
+    }).collect()
+}
+
+fn calculate_safety_factor(robots: &[Robot], width: i32, height: i32) -> i32 {
+    let mut quadrants = [0; 4];
+
+    for robot in robots {
+        if robot.pos.x == width / 2 || robot.pos.y == height / 2 {
+            continue;
+        }
+
+        let quadrant = match (robot.pos.x > width / 2, robot.pos.y > height / 2) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (false, true) => 2,
+            (true, true) => 3,
+        };
+
+        quadrants[quadrant] += 1;
+    }
+
+    quadrants.iter().product()
+}
+
+pub fn calculate_grid(robots: &[Robot], seconds: i32, width: i32, height: i32) -> (Vec<Vec<char>>, bool) {
+    let mut grid = vec![vec!['.'; width as usize]; height as usize];
+    let mut has_overlaps = false;
+
+    for robot in robots {
+        let new_x = ((robot.pos.x + seconds * robot.vel.x) % width + width) % width;
+        let new_y = ((robot.pos.y + seconds * robot.vel.y) % height + height) % height;
+
+        match grid[new_y as usize][new_x as usize] {
+            '.' => grid[new_y as usize][new_x as usize] = '█',
+            _ => {
+                grid[new_y as usize][new_x as usize] = '▒';
+                has_overlaps = true;
+            }
+        }
+    }
+
+    (grid, has_overlaps)
+}
+
+pub fn render_state(grid: &[Vec<char>], seconds: i32) -> String {
+    let mut out = format!("Time: {} seconds\n", seconds);
+    out.push_str(&format!("{}\n", "-".repeat(grid[0].len())));
+    for row in grid {
+        out.push_str(&format!("{}\n", row.iter().collect::<String>()));
+    }
+    out.push_str(&format!("{}\n", "-".repeat(grid[0].len())));
+
+    let overlaps = grid.iter()
+        .flat_map(|row| row.iter())
+        .filter(|&&c| c == '▒')
+        .count();
+
+    if overlaps == 0 {
+        out.push_str("NO OVERLAPS IN THIS STATE!\n");
+    } else {
+        out.push_str(&format!("Number of positions with overlaps: {}\n", overlaps));
+    }
+    out
+}
+
+pub fn print_state(grid: &[Vec<char>], seconds: i32) {
+    print!("{}", render_state(grid, seconds));
+    println!("\nPress 'w' for next step, Enter for next non-overlapping state, 'q' to quit");
+}
+
+pub fn find_next_no_overlap(robots: &[Robot], current_time: i32, width: i32, height: i32, max_search: i32) -> Option<i32> {
+    for time in (current_time + 1)..=(current_time + max_search) {
+        let (_, has_overlaps) = calculate_grid(robots, time, width, height);
+        if !has_overlaps {
+            return Some(time);
+        }
+    }
+    None
+}
+
+pub fn part_1(input: &str) -> i32 {
+    part_1_with_size(input, WIDTH, HEIGHT)
+}
+
+pub fn part_1_with_size(input: &str, width: i32, height: i32) -> i32 {
+    let robots = parse_input(input);
+    let final_positions = simulate_robots(&robots, width, height, 100);
+    calculate_safety_factor(&final_positions, width, height)
+}
+
+// The tree shows up the first time no two robots share a tile
+pub fn part_2(input: &str) -> i32 {
+    let robots = parse_input(input);
+    find_next_no_overlap(&robots, -1, WIDTH, HEIGHT, WIDTH * HEIGHT)
+        .expect("Robots always overlap")
+}
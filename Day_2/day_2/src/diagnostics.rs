@@ -0,0 +1,120 @@
+use crate::policy::{Rule, SafetyPolicy};
+use crate::{first_violation, removals};
+
+// Why each report that isn't safe as it stands fails, and for the ones the
+// dampener rescues, which levels it takes out.
+
+pub struct Diagnosis {
+    // 1-based line in the input
+    pub line: usize,
+    pub levels: Vec<i32>,
+    // Index of the first level of the offending pair, and the rule broken
+    pub index: usize,
+    pub rule: Rule,
+    // Indices of the levels the dampener removes, if that makes it safe
+    pub fixed_by: Option<Vec<usize>>,
+}
+
+impl Diagnosis {
+    pub fn is_safe(&self) -> bool {
+        self.fixed_by.is_some()
+    }
+
+    fn explain(&self, policy: &SafetyPolicy) -> String {
+        if self.rule == Rule::TooShort {
+            return "fewer than two levels".to_string();
+        }
+        let (from, to) = (self.levels[self.index], self.levels[self.index + 1]);
+        let pair = format!("levels {} and {} ({} -> {})", self.index, self.index + 1, from, to);
+        let step = (to as i64 - from as i64).abs();
+        match self.rule {
+            Rule::TooShort => unreachable!(),
+            Rule::ZeroStep => format!("{}: no change", pair),
+            Rule::DirectionChange => format!("{}: changes direction", pair),
+            Rule::StepTooLarge => format!("{}: step of {} is more than {}", pair, step, policy.max_step),
+            Rule::StepTooSmall => format!("{}: step of {} is less than {}", pair, step, policy.min_step),
+        }
+    }
+}
+
+// Every report in the input that isn't safe without the dampener
+pub fn diagnose(input: &str, policy: &SafetyPolicy) -> Vec<Diagnosis> {
+    let mut diagnoses = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let levels: Vec<i32> = line
+            .split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        let Some((index, rule)) = first_violation(&levels, policy) else {
+            continue;
+        };
+
+        let fixed_by = removals(&levels, policy).filter(|removed| removed.len() <= policy.dampener);
+        diagnoses.push(Diagnosis {
+            line: i + 1,
+            levels,
+            index,
+            rule,
+            fixed_by,
+        });
+    }
+
+    diagnoses
+}
+
+pub fn to_text(diagnoses: &[Diagnosis], policy: &SafetyPolicy) -> String {
+    let mut out = String::new();
+    for diagnosis in diagnoses {
+        let levels: Vec<String> = diagnosis.levels.iter().map(|l| l.to_string()).collect();
+        out.push_str(&format!("line {}: {}\n", diagnosis.line, levels.join(" ")));
+        out.push_str(&format!("  {}\n", diagnosis.explain(policy)));
+
+        match &diagnosis.fixed_by {
+            Some(removed) => {
+                let removed: Vec<String> = removed
+                    .iter()
+                    .map(|&i| format!("level {} ({})", i, diagnosis.levels[i]))
+                    .collect();
+                out.push_str(&format!("  safe after removing {}\n", removed.join(", ")));
+            }
+            None => out.push_str("  unsafe\n"),
+        }
+    }
+
+    let dampened = diagnoses.iter().filter(|d| d.is_safe()).count();
+    out.push_str(&format!(
+        "{} reports not safe as they stand: {} fixed by the dampener, {} unsafe\n",
+        diagnoses.len(),
+        dampened,
+        diagnoses.len() - dampened
+    ));
+    out
+}
+
+fn json_list(values: impl Iterator<Item = String>) -> String {
+    format!("[{}]", values.collect::<Vec<_>>().join(","))
+}
+
+// One object per report, with the offending pair, rule and any fix
+pub fn to_json(diagnoses: &[Diagnosis]) -> String {
+    let entries: Vec<String> = diagnoses
+        .iter()
+        .map(|d| {
+            let fixed_by = match &d.fixed_by {
+                Some(removed) => json_list(removed.iter().map(|i| i.to_string())),
+                None => "null".to_string(),
+            };
+            format!(
+                "  {{\"line\":{},\"levels\":{},\"index\":{},\"rule\":\"{}\",\"safe\":{},\"removed\":{}}}",
+                d.line,
+                json_list(d.levels.iter().map(|l| l.to_string())),
+                d.index,
+                d.rule.name(),
+                d.is_safe(),
+                fixed_by
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
@@ -0,0 +1,319 @@
+pub mod diagnostics;
+pub mod policy;
+pub mod stream;
+
+use std::collections::HashMap;
+
+use policy::{Direction, Rule, SafetyPolicy};
+
+// The first pair of neighbouring levels that breaks the policy, ignoring the
+// dampener: (index of the first level of the pair, rule broken)
+pub fn first_violation(levels: &[i32], policy: &SafetyPolicy) -> Option<(usize, Rule)> {
+    if levels.len() < 2 {
+        return Some((0, Rule::TooShort));
+    }
+
+    // Determine if sequence should be increasing or decreasing, going by the
+    // first level that differs from the one before
+    let sign = match policy.direction {
+        Direction::Increasing => 1,
+        Direction::Decreasing => -1,
+        Direction::Either => {
+            let first_step = levels.windows(2).map(|w| w[1] as i64 - w[0] as i64).find(|&d| d != 0);
+            if first_step.unwrap_or(0) < 0 { -1 } else { 1 }
+        }
+    };
+
+    // Check each adjacent pair against the step range and direction
+    for (i, window) in levels.windows(2).enumerate() {
+        if let Some(rule) = policy.broken_rule(window[1] as i64 - window[0] as i64, sign) {
+            return Some((i, rule));
+        }
+    }
+
+    None
+}
+
+fn check_report_safety(levels: &[i32], policy: &SafetyPolicy) -> bool {
+    first_violation(levels, policy).is_none()
+}
+
+// Indices of the fewest levels to remove for the rest to be safe, or None if
+// no two levels can be kept. The kept levels form a subsequence whose
+// neighbours all pass, so in each direction this finds the longest such chain
+// in one pass. A level extends the longest chain ending on a value one allowed
+// step away, looked up by value while the step range is narrow and by
+// scanning the earlier levels once that's cheaper.
+pub fn removals(levels: &[i32], policy: &SafetyPolicy) -> Option<Vec<usize>> {
+    // Every step a chain may take, counted from the bounds rather than listed
+    let steps = || (policy.min_step..=policy.max_step).chain((!policy.strict).then_some(0));
+    let step_count = (policy.max_step as i64 - policy.min_step as i64 + 1).max(0) + !policy.strict as i64;
+
+    // (length, level before) of the longest chain ending at each level
+    let mut best: Vec<(usize, Option<usize>)> = Vec::new();
+    for &sign in policy.signs() {
+        // value -> (length, index) of the longest chain ending on that value
+        let mut chain_ending: HashMap<i32, (usize, usize)> = HashMap::new();
+        let mut chains: Vec<(usize, Option<usize>)> = Vec::with_capacity(levels.len());
+        for (i, &level) in levels.iter().enumerate() {
+            let previous = if step_count <= i as i64 {
+                steps()
+                    .filter_map(|step| i32::try_from(level as i64 - sign as i64 * step as i64).ok())
+                    .filter_map(|before| chain_ending.get(&before))
+                    .max()
+                    .copied()
+            } else {
+                (0..i)
+                    .filter(|&j| policy.allows(level as i64 - levels[j] as i64, sign))
+                    .map(|j| (chains[j].0, j))
+                    .max()
+            };
+            let chain = match previous {
+                Some((length, j)) => (length + 1, Some(j)),
+                None => (1, None),
+            };
+            chains.push(chain);
+            let ending = chain_ending.entry(level).or_insert((0, i));
+            *ending = (*ending).max((chain.0, i));
+        }
+
+        let longest = |chains: &[(usize, Option<usize>)]| chains.iter().map(|c| c.0).max().unwrap_or(0);
+        if longest(&chains) > longest(&best) {
+            best = chains;
+        }
+    }
+
+    // Walk back from the end of the longest chain to find the levels kept
+    let (mut end, &(length, _)) = best.iter().enumerate().max_by_key(|(i, c)| (c.0, std::cmp::Reverse(*i)))?;
+    if length < 2 {
+        return None;
+    }
+    let mut kept = vec![false; levels.len()];
+    loop {
+        kept[end] = true;
+        match best[end].1 {
+            Some(previous) => end = previous,
+            None => break,
+        }
+    }
+    Some((0..levels.len()).filter(|&i| !kept[i]).collect())
+}
+
+// How many levels `removals` takes out
+pub fn min_removals(levels: &[i32], policy: &SafetyPolicy) -> Option<usize> {
+    removals(levels, policy).map(|removed| removed.len())
+}
+
+// Whether removing at most one level makes the report safe, in one pass with
+// no allocation. For each direction three things are tracked about the levels
+// seen so far: they pass as they are; they pass with one earlier level
+// removed, ending on the latest level; they pass with the latest level
+// removed, ending on the one before.
+fn check_report_safety_with_dampener(levels: &[i32], policy: &SafetyPolicy) -> bool {
+    match levels.len() {
+        0 | 1 => return false,
+        // Removing either level would leave too few
+        2 => return check_report_safety(levels, policy),
+        _ => {}
+    }
+
+    let signs = policy.signs();
+    // Per direction: (as they are, one removed earlier, latest removed)
+    let mut states = [(true, false, true); 2];
+
+    for i in 1..levels.len() {
+        let level = levels[i];
+        for (state, &sign) in states.iter_mut().zip(signs) {
+            let (intact, removed_earlier, removed_latest) = *state;
+            let follows_latest = policy.allows(level as i64 - levels[i - 1] as i64, sign);
+            // With the level before removed, this one follows the one before
+            // that, or starts the report if that was the first level
+            let follows_skipped = i < 2 || policy.allows(level as i64 - levels[i - 2] as i64, sign);
+
+            *state = (
+                intact && follows_latest,
+                (removed_earlier && follows_latest) || (removed_latest && follows_skipped),
+                intact,
+            );
+        }
+    }
+
+    states[..signs.len()]
+        .iter()
+        .any(|&(intact, removed_earlier, removed_latest)| intact || removed_earlier || removed_latest)
+}
+
+// Safe under the policy, using its dampener if the report needs it
+pub fn is_report_safe(levels: &[i32], policy: &SafetyPolicy) -> bool {
+    match policy.dampener {
+        0 => check_report_safety(levels, policy),
+        1 => check_report_safety_with_dampener(levels, policy),
+        budget => min_removals(levels, policy).is_some_and(|removals| removals <= budget),
+    }
+}
+
+// Parses one report into `levels`, reusing its allocation
+pub(crate) fn parse_levels(line: &str, levels: &mut Vec<i32>) {
+    levels.clear();
+    levels.extend(line.split_whitespace().filter_map(|s| s.parse::<i32>().ok()));
+}
+
+fn process_input<F>(input: &str, mut process_line: F)
+where
+    F: FnMut(&[i32]),
+{
+    let mut levels = Vec::new();
+    for line in input.lines() {
+        parse_levels(line, &mut levels);
+        process_line(&levels);
+    }
+}
+
+// Reports that are safe under the policy
+pub fn count_safe(input: &str, policy: &SafetyPolicy) -> i32 {
+    let mut safe_reports = 0;
+
+    process_input(input, |levels| {
+        if is_report_safe(levels, policy) {
+            safe_reports += 1;
+        }
+    });
+
+    safe_reports
+}
+
+pub fn part_1(input: &str) -> i32 {
+    count_safe(input, &SafetyPolicy::PART_1)
+}
+
+pub fn part_2(input: &str) -> i32 {
+    count_safe(input, &SafetyPolicy::PART_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Direction;
+
+    // Small xorshift so the reports are the same every run
+    pub(crate) struct Rng(pub(crate) u64);
+
+    impl Rng {
+        pub(crate) fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+
+        // Mostly small steps, so plenty of reports are safe or nearly so
+        pub(crate) fn report(&mut self, max_len: u64) -> Vec<i32> {
+            let len = self.below(max_len + 1) as usize;
+            let mut levels = vec![self.below(20) as i32];
+            while levels.len() < len {
+                let step = self.below(11) as i32 - 5;
+                levels.push(levels.last().unwrap() + step);
+            }
+            levels.truncate(len);
+            levels
+        }
+    }
+
+    pub(crate) fn policies() -> Vec<SafetyPolicy> {
+        let base = SafetyPolicy::PART_2;
+        vec![
+            base,
+            SafetyPolicy { strict: false, ..base },
+            SafetyPolicy { direction: Direction::Increasing, ..base },
+            SafetyPolicy { direction: Direction::Decreasing, ..base },
+            SafetyPolicy { min_step: 2, max_step: 4, ..base },
+            SafetyPolicy { min_step: 1, max_step: 1, strict: false, ..base },
+            // Wider than any report is long, so removals scans earlier levels instead of looking up values
+            SafetyPolicy { max_step: 1000, ..base },
+        ]
+    }
+
+    fn without(levels: &[i32], removed: &[usize]) -> Vec<i32> {
+        levels.iter().enumerate().filter(|(i, _)| !removed.contains(i)).map(|(_, &l)| l).collect()
+    }
+
+    #[test]
+    fn dampener_matches_single_removal() {
+        let mut rng = Rng(0x2024_1202);
+        for policy in policies() {
+            for _ in 0..3000 {
+                let levels = rng.report(9);
+                let expected = check_report_safety(&levels, &policy)
+                    || (0..levels.len()).any(|i| check_report_safety(&without(&levels, &[i]), &policy));
+                assert_eq!(
+                    check_report_safety_with_dampener(&levels, &policy),
+                    expected,
+                    "{:?} under {}",
+                    levels,
+                    policy
+                );
+            }
+        }
+    }
+
+    // Every way of choosing `k` of `n` indices
+    fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+        if k == 0 {
+            return vec![Vec::new()];
+        }
+        (k - 1..n)
+            .flat_map(|last| {
+                subsets(last, k - 1).into_iter().map(move |mut subset| {
+                    subset.push(last);
+                    subset
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn removals_match_subset_enumeration() {
+        let mut rng = Rng(0x0040);
+        for policy in policies() {
+            for _ in 0..1500 {
+                let levels = rng.report(8);
+                let fewest = (0..=3).find(|&k| {
+                    subsets(levels.len(), k).iter().any(|removed| check_report_safety(&without(&levels, removed), &policy))
+                });
+
+                match fewest {
+                    Some(k) => assert_eq!(min_removals(&levels, &policy), Some(k), "{:?} under {}", levels, policy),
+                    None => assert!(min_removals(&levels, &policy).is_none_or(|k| k > 3), "{:?} under {}", levels, policy),
+                }
+                if let Some(removed) = removals(&levels, &policy) {
+                    assert!(check_report_safety(&without(&levels, &removed), &policy), "{:?} less {:?}", levels, removed);
+                }
+                for dampener in 0..=3 {
+                    let policy = SafetyPolicy { dampener, ..policy };
+                    assert_eq!(is_report_safe(&levels, &policy), fewest.is_some_and(|k| k <= dampener), "{:?} under {}", levels, policy);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dampener_examples() {
+        let cases = [
+            ("7 6 4 2 1", true),
+            ("1 2 7 8 9", false),
+            ("9 7 6 2 1", false),
+            ("1 3 2 4 5", true),
+            ("8 6 4 4 1", true),
+            ("1 3 6 7 9", true),
+            ("5 1 2 3 4", true),
+            ("1 2 3 4 9", true),
+            ("1 9", false),
+            ("5", false),
+        ];
+        for (line, safe) in cases {
+            let mut levels = Vec::new();
+            parse_levels(line, &mut levels);
+            assert_eq!(check_report_safety_with_dampener(&levels, &SafetyPolicy::PART_2), safe, "{}", line);
+        }
+    }
+}
@@ -1,105 +1,57 @@
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::env;
+use std::fs;
 use std::path::Path;
+use std::thread;
 
-fn check_report_safety(levels: &Vec<i32>) -> bool {
-    if levels.len() < 2 {
-        return false;
-    }
-
-    // Determine if sequence should be increasing or decreasing
-    let is_increasing = levels[1] > levels[0];
-
-    // Check each adjacent pair
-    for window in levels.windows(2) {
-        let diff = (window[1] - window[0]).abs();
-
-        // Check if difference is within valid range (1-3)
-        if diff < 1 || diff > 3 {
-            return false;
-        }
-
-        // Check if sequence maintains its direction
-        if is_increasing && window[1] <= window[0] {
-            return false;
-        }
-        if !is_increasing && window[1] >= window[0] {
-            return false;
-        }
-    }
-
-    true
-}
+use day_2::diagnostics;
+use day_2::policy::SafetyPolicy;
+use day_2::stream;
 
-fn check_report_safety_with_dampener(levels: &Vec<i32>) -> bool {
-    if levels.len() < 2 {
-        return false;
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let input_file = args
+        .get(1)
+        .filter(|a| !a.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "../../input/day2/sample.txt".to_string());
+
+    // --stream reads the file in chunks checked on --threads <n> threads
+    // (all cores by default) instead of reading it whole
+    if args.iter().any(|a| a == "--stream") {
+        let threads = match args.iter().position(|a| a == "--threads") {
+            Some(i) => args.get(i + 1).and_then(|n| n.parse().ok()).expect("--threads takes a number"),
+            None => thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+        let policies = [SafetyPolicy::PART_1, SafetyPolicy::PART_2];
+        let counts = stream::process_file(Path::new(&input_file), &policies, threads, stream::DEFAULT_CHUNK).expect("Failed to read input file");
+        println!("Part 1: {}", counts[0]);
+        println!("Part 2: {}", counts[1]);
+        return;
     }
 
-    for i in 0..levels.len() -1 {
-        if !(1..=3).contains(&(levels[i] - levels[i + 1])) {
-            return [i, i+1].iter().any(|&j| {
-                let modified: Vec<i32> = levels
-                    .iter()
-                    .enumerate()
-                    .filter(|&(idx, _)| idx != j)
-                    .map(|(_, &x)| x)
-                    .collect();
-                check_report_safety(&modified)
-            })
+    let contents = fs::read_to_string(&input_file).expect("Failed to read input file");
+
+    // --diagnose explains every report that isn't safe as it stands, under
+    // part 2's rules unless policy flags say otherwise; --json for JSON
+    if args.iter().any(|a| a == "--diagnose") {
+        let policy = SafetyPolicy::from_args(&args, SafetyPolicy::PART_2).unwrap_or_else(|e| panic!("Bad policy: {}", e));
+        let diagnoses = diagnostics::diagnose(&contents, &policy);
+        if args.iter().any(|a| a == "--json") {
+            print!("{}", diagnostics::to_json(&diagnoses));
+        } else {
+            print!("{}", diagnostics::to_text(&diagnoses, &policy));
         }
+        return;
     }
 
-    true
-}
-
-fn process_file<F>(filepath: &str, mut process_line: F)
-where
-    F: FnMut(Vec<i32>),
-{
-    let path = Path::new(filepath);
-    let file = File::open(path).unwrap();
-    let reader = io::BufReader::new(file);
+    println!("Part 1: {}", day_2::part_1(&contents));
+    println!("Part 2: {}", day_2::part_2(&contents));
 
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let levels: Vec<i32> = line
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
-        process_line(levels);
+    // Policy flags (or --policy <file>) also count the reports safe under
+    // that policy, starting from part 1's rules
+    let policy_flags = ["--policy", "--min-step", "--max-step", "--direction", "--non-strict", "--dampener"];
+    if args.iter().any(|a| policy_flags.contains(&a.as_str())) {
+        let policy = SafetyPolicy::from_args(&args, SafetyPolicy::PART_1).unwrap_or_else(|e| panic!("Bad policy: {}", e));
+        println!("Safe with {}: {}", policy, day_2::count_safe(&contents, &policy));
     }
 }
-
-fn part_1(filepath: &str) {
-    let mut safe_reports = 0;
-
-    process_file(filepath, |levels| {
-        if check_report_safety(&levels) {
-            safe_reports += 1;
-        }
-    });
-
-    println!("Part 1: {}", safe_reports);
-}
-
-fn part_2(filepath: &str) {
-    let mut safe_reports = 0;
-
-    process_file(filepath, |mut levels| {
-        let og_levels = levels.clone();
-        levels.reverse();
-
-        if check_report_safety_with_dampener(&levels) || check_report_safety_with_dampener(&og_levels) {
-            safe_reports += 1;
-        }
-    });
-
-    println!("Part 2: {}", safe_reports);
-}
-
-fn main() {
-    let input_file = "../../input/day2/sample.txt";
-    part_1(input_file);
-    part_2(input_file);
-}
\ No newline at end of file
@@ -0,0 +1,238 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    // Whichever way the report starts out
+    Either,
+}
+
+// Why a report isn't safe as it stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    // Fewer than two levels
+    TooShort,
+    // A level repeats the one before under a strict policy
+    ZeroStep,
+    // A step goes against the report's direction
+    DirectionChange,
+    StepTooLarge,
+    StepTooSmall,
+}
+
+impl Rule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::TooShort => "too_short",
+            Rule::ZeroStep => "zero_step",
+            Rule::DirectionChange => "direction_change",
+            Rule::StepTooLarge => "step_too_large",
+            Rule::StepTooSmall => "step_too_small",
+        }
+    }
+}
+
+// What makes a report safe: every step between neighbouring levels is
+// between min_step and max_step in the allowed direction, after removing at
+// most dampener levels. Non-strict policies also let a level repeat the one
+// before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    pub min_step: i32,
+    pub max_step: i32,
+    pub direction: Direction,
+    pub strict: bool,
+    pub dampener: usize,
+}
+
+impl SafetyPolicy {
+    pub const PART_1: SafetyPolicy = SafetyPolicy {
+        min_step: 1,
+        max_step: 3,
+        direction: Direction::Either,
+        strict: true,
+        dampener: 0,
+    };
+
+    pub const PART_2: SafetyPolicy = SafetyPolicy {
+        dampener: 1,
+        ..SafetyPolicy::PART_1
+    };
+
+    // The rule a step breaks going the given way (1 up, -1 down), if any
+    pub fn broken_rule(&self, diff: i64, sign: i32) -> Option<Rule> {
+        if diff == 0 {
+            return self.strict.then_some(Rule::ZeroStep);
+        }
+        if diff.signum() != sign as i64 {
+            Some(Rule::DirectionChange)
+        } else if diff.abs() > self.max_step as i64 {
+            Some(Rule::StepTooLarge)
+        } else if diff.abs() < self.min_step as i64 {
+            Some(Rule::StepTooSmall)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn allows(&self, diff: i64, sign: i32) -> bool {
+        self.broken_rule(diff, sign).is_none()
+    }
+
+    // The ways a report may go: 1 up, -1 down
+    pub(crate) fn signs(&self) -> &'static [i32] {
+        match self.direction {
+            Direction::Increasing => &[1],
+            Direction::Decreasing => &[-1],
+            Direction::Either => &[1, -1],
+        }
+    }
+
+    // Lines of `key = value` with # comments, on top of part 1's rules:
+    //   min_step = 1
+    //   max_step = 3
+    //   direction = increasing | decreasing | either
+    //   strict = true | false
+    //   dampener = 1
+    pub fn parse(text: &str) -> Result<Self, String> {
+        SafetyPolicy::parse_onto(text, SafetyPolicy::PART_1)
+    }
+
+    // The same, with the keys the text sets applied on top of `base`
+    pub fn parse_onto(text: &str, base: SafetyPolicy) -> Result<Self, String> {
+        let mut policy = base;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected key = value", i + 1));
+            };
+            policy.set(key.trim(), value.trim()).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        }
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn load(path: &Path, base: SafetyPolicy) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        SafetyPolicy::parse_onto(&text, base).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    // The file's keys apply on top of `base`, then the flags on top of that:
+    //   --policy <file> --min-step <n> --max-step <n> --direction <d> --non-strict --dampener <k>
+    pub fn from_args(args: &[String], base: SafetyPolicy) -> Result<Self, String> {
+        let mut policy = base;
+        if let Some(path) = flag_value(args, "--policy")? {
+            policy = SafetyPolicy::load(Path::new(path), base)?;
+        }
+        for (flag, key) in [
+            ("--min-step", "min_step"),
+            ("--max-step", "max_step"),
+            ("--direction", "direction"),
+            ("--dampener", "dampener"),
+        ] {
+            if let Some(value) = flag_value(args, flag)? {
+                policy.set(key, value).map_err(|e| format!("{}: {}", flag, e))?;
+            }
+        }
+        if args.iter().any(|a| a == "--non-strict") {
+            policy.strict = false;
+        }
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let number = || value.parse::<i32>().map_err(|_| format!("{} is not a number", value));
+        match key {
+            "min_step" => self.min_step = number()?,
+            "max_step" => self.max_step = number()?,
+            "dampener" => self.dampener = value.parse().map_err(|_| format!("{} is not a count", value))?,
+            "strict" => self.strict = value.parse().map_err(|_| format!("{} is not true or false", value))?,
+            "direction" => {
+                self.direction = match value {
+                    "increasing" => Direction::Increasing,
+                    "decreasing" => Direction::Decreasing,
+                    "either" => Direction::Either,
+                    _ => return Err(format!("unknown direction {}", value)),
+                }
+            }
+            _ => return Err(format!("unknown setting {}", key)),
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.min_step < 1 || self.max_step < self.min_step {
+            return Err(format!(
+                "steps must satisfy 1 <= min_step <= max_step, got {}..={}",
+                self.min_step, self.max_step
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SafetyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Increasing => "increasing",
+            Direction::Decreasing => "decreasing",
+            Direction::Either => "either direction",
+        };
+        let strict = if self.strict { "strict" } else { "non-strict" };
+        write!(
+            f,
+            "steps {}..={}, {}, {}, dampener {}",
+            self.min_step, self.max_step, direction, strict, self.dampener
+        )
+    }
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|a| a == flag) {
+        Some(i) => match args.get(i + 1) {
+            Some(value) => Ok(Some(value)),
+            None => Err(format!("{} needs a value", flag)),
+        },
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn file_keys_apply_on_top_of_base() {
+        let path = std::env::temp_dir().join(format!("day_2_policy_{}", std::process::id()));
+        fs::write(&path, "# wider steps only\nmax_step = 5\n").unwrap();
+        let path_arg = path.to_str().unwrap();
+
+        // The file leaves the dampener alone, so part 2's carries through
+        let policy = SafetyPolicy::from_args(&args(&["--policy", path_arg]), SafetyPolicy::PART_2);
+        assert_eq!(policy, Ok(SafetyPolicy { max_step: 5, ..SafetyPolicy::PART_2 }));
+
+        // Flags still win over the file
+        let policy = SafetyPolicy::from_args(&args(&["--policy", path_arg, "--max-step", "4"]), SafetyPolicy::PART_1);
+        assert_eq!(policy, Ok(SafetyPolicy { max_step: 4, ..SafetyPolicy::PART_1 }));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn parse_starts_from_part_1() {
+        assert_eq!(SafetyPolicy::parse("dampener = 2"), Ok(SafetyPolicy { dampener: 2, ..SafetyPolicy::PART_1 }));
+        assert!(SafetyPolicy::parse("min_step = 4").is_err());
+        assert!(SafetyPolicy::parse("max_step 4").is_err());
+    }
+}
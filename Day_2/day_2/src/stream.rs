@@ -0,0 +1,214 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::policy::SafetyPolicy;
+use crate::{is_report_safe, parse_levels};
+
+// Counts safe reports in files too big to read whole. The file is read in
+// chunks cut at line ends and the chunks are checked by worker threads. A
+// fixed set of chunk buffers is passed around and reused, so memory stays at
+// about (2 x threads) x chunk bytes however big the file is.
+
+pub const DEFAULT_CHUNK: usize = 4 * 1024 * 1024;
+
+// Safe reports in the file under each of the policies, in one read of
+// `chunk`-byte pieces
+pub fn process_file(path: &Path, policies: &[SafetyPolicy], threads: usize, chunk: usize) -> io::Result<Vec<usize>> {
+    let mut file = File::open(path)?;
+    let threads = threads.max(1);
+    let chunk = chunk.max(1);
+
+    let (work_tx, work_rx) = mpsc::sync_channel::<Vec<u8>>(threads);
+    let (free_tx, free_rx) = mpsc::channel::<Vec<u8>>();
+    for _ in 0..threads * 2 {
+        free_tx.send(Vec::with_capacity(chunk)).unwrap();
+    }
+    let work_rx = Mutex::new(work_rx);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                let free_tx = free_tx.clone();
+                let work_rx = &work_rx;
+                scope.spawn(move || check_chunks(work_rx, &free_tx, policies))
+            })
+            .collect();
+
+        let read = read_chunks(&mut file, chunk, work_tx, &free_tx, &free_rx);
+
+        let mut totals = vec![0; policies.len()];
+        for worker in workers {
+            for (total, count) in totals.iter_mut().zip(worker.join().unwrap()) {
+                *total += count;
+            }
+        }
+        read.map(|()| totals)
+    })
+}
+
+// Fills free buffers from the file, each ending on a whole line
+fn read_chunks(
+    file: &mut File,
+    chunk: usize,
+    work_tx: SyncSender<Vec<u8>>,
+    free_tx: &Sender<Vec<u8>>,
+    free_rx: &Receiver<Vec<u8>>,
+) -> io::Result<()> {
+    // The partial line left over from the previous chunk
+    let mut carry = Vec::new();
+
+    loop {
+        let mut buffer = free_rx.recv().expect("chunk buffers are never dropped");
+        buffer.clear();
+        buffer.append(&mut carry);
+
+        let read = file.by_ref().take(chunk as u64).read_to_end(&mut buffer)?;
+        if read == 0 {
+            if !buffer.is_empty() {
+                let _ = work_tx.send(buffer);
+            }
+            return Ok(());
+        }
+
+        match buffer.iter().rposition(|&b| b == b'\n') {
+            Some(end) => {
+                carry.extend_from_slice(&buffer[end + 1..]);
+                buffer.truncate(end + 1);
+                if work_tx.send(buffer).is_err() {
+                    return Ok(());
+                }
+            }
+            // A line longer than a chunk; keep reading until it ends
+            None => {
+                carry.append(&mut buffer);
+                let _ = free_tx.send(buffer);
+            }
+        }
+    }
+}
+
+fn check_chunks(work_rx: &Mutex<Receiver<Vec<u8>>>, free_tx: &Sender<Vec<u8>>, policies: &[SafetyPolicy]) -> Vec<usize> {
+    let mut counts = vec![0; policies.len()];
+    let mut levels = Vec::new();
+
+    loop {
+        let Ok(chunk) = work_rx.lock().unwrap().recv() else {
+            return counts;
+        };
+
+        for line in chunk.split(|&b| b == b'\n') {
+            let Ok(line) = str::from_utf8(line) else {
+                continue;
+            };
+            parse_levels(line, &mut levels);
+            if levels.is_empty() {
+                continue;
+            }
+            for (count, policy) in counts.iter_mut().zip(policies) {
+                if is_report_safe(&levels, policy) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let _ = free_tx.send(chunk);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::count_safe;
+    use crate::tests::Rng;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // Writes `contents` to a file of its own under the temp dir, removed on drop
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("day_2_stream_{}_{}", std::process::id(), name));
+            fs::write(&path, contents).unwrap();
+            TempFile(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    // Small so chunk edges come up without megabytes of input
+    const CHUNK: usize = 64 * 1024;
+
+    // The stream's counts against count_safe on the same text, on one thread and several
+    fn check(name: &str, contents: &str) {
+        check_in(name, contents, CHUNK);
+    }
+
+    fn check_in(name: &str, contents: &str, chunk: usize) {
+        let file = TempFile::new(name, contents);
+        let policies = [SafetyPolicy::PART_1, SafetyPolicy::PART_2];
+        let expected: Vec<usize> = policies.iter().map(|p| count_safe(contents, p) as usize).collect();
+        for threads in [1, 3] {
+            assert_eq!(process_file(&file.0, &policies, threads, chunk).unwrap(), expected, "{} on {} threads", name, threads);
+        }
+    }
+
+    fn random_reports(rng: &mut Rng, count: usize) -> String {
+        (0..count)
+            .map(|_| {
+                let levels = rng.report(8);
+                levels.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(" ") + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blank_lines_and_no_final_newline() {
+        check("blank", "7 6 4 2 1\n\n1 2 7 8 9\n\n\n1 3 2 4 5\n   \n8 6 4 4 1\n1 3 6 7 9");
+        check("empty", "");
+        check("newlines", "\n\n\n");
+        check("single", "1 2 3");
+    }
+
+    #[test]
+    fn chunks_shorter_than_a_line() {
+        // Nearly every report spans several chunks
+        let mut rng = Rng(0xc0de);
+        check_in("tiny", &random_reports(&mut rng, 500), 5);
+    }
+
+    #[test]
+    fn many_chunks() {
+        // Several chunks' worth of short reports, cut mid-line at every chunk edge
+        let mut rng = Rng(0x5eed);
+        let mut contents = random_reports(&mut rng, 3 * CHUNK / 12);
+        contents.push_str("1 2 4 5");
+        assert!(contents.len() > 2 * CHUNK);
+        check("many", &contents);
+    }
+
+    #[test]
+    fn lines_longer_than_a_chunk() {
+        // One safe and one unsafe report each longer than a chunk, among short ones
+        let long: Vec<String> = (0..CHUNK as i32 / 2).map(|l| l.to_string()).collect();
+        let long = long.join(" ");
+        assert!(long.len() > CHUNK);
+        let mut rng = Rng(0xfeed);
+        let mut contents = random_reports(&mut rng, 1000);
+        contents.push_str(&long);
+        contents.push('\n');
+        contents.push_str(&random_reports(&mut rng, 1000));
+        contents.push_str(&long);
+        contents.push_str(" 0 -5");
+        check("long", &contents);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::interpreter::Interpreter;
+use crate::number::Number;
+
+// Every instruction the interpreter ran, where it sits in the memory dump and
+// what it did, plus the dump itself with the instructions picked out.
+
+pub struct Annotation {
+    // Byte offset into the dump, and length in bytes
+    pub offset: usize,
+    pub len: usize,
+    pub name: String,
+    pub operands: Vec<Number>,
+    // Whether the machine was enabled when it ran
+    pub enabled: bool,
+    // What it added to the enabled-only total
+    pub contribution: Number,
+}
+
+pub fn annotate(interpreter: &mut Interpreter, contents: &[u8]) -> Vec<Annotation> {
+    let mut runs = Vec::new();
+    interpreter.feed_with(contents, |run| runs.push(run));
+
+    runs.into_iter()
+        .map(|run| Annotation {
+            offset: run.found.offset as usize,
+            len: run.found.len,
+            name: interpreter.name(run.found.instruction).to_string(),
+            operands: run.found.operands().to_vec(),
+            enabled: run.enabled,
+            contribution: run.contribution,
+        })
+        .collect()
+}
+
+// One line per instruction: offset, name, operands, enabled, contribution
+pub fn list(annotations: &[Annotation]) -> String {
+    let operands: Vec<String> = annotations
+        .iter()
+        .map(|a| a.operands.iter().map(|o| o.to_string()).collect::<Vec<_>>().join(","))
+        .collect();
+    let name_width = annotations.iter().map(|a| a.name.len()).chain(["name".len()]).max().unwrap_or(0);
+    let operand_width = operands.iter().map(|o| o.len()).chain(["operands".len()]).max().unwrap_or(0);
+
+    let mut out = format!(
+        "{:>8}  {:<name_width$}  {:<operand_width$}  {:<7}  {:>12}\n",
+        "offset", "name", "operands", "enabled", "contribution"
+    );
+    for (annotation, operands) in annotations.iter().zip(&operands) {
+        out.push_str(&format!(
+            "{:>8}  {:<name_width$}  {:<operand_width$}  {:<7}  {:>12}\n",
+            annotation.offset,
+            annotation.name,
+            operands,
+            if annotation.enabled { "yes" } else { "no" },
+            annotation.contribution
+        ));
+    }
+    out
+}
+
+const ENABLED: &str = "\x1b[1;32m";
+const DISABLED: &str = "\x1b[1;31m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+// The dump with instructions run while enabled in green, those run while
+// disabled in red and everything else dimmed. Without colour, enabled
+// instructions are wrapped in [ ] and disabled ones in < >.
+pub fn render(annotations: &[Annotation], contents: &[u8], color: bool) -> String {
+    let mut out = String::with_capacity(contents.len() * 2);
+    let mut at = 0;
+
+    for annotation in annotations {
+        let noise = String::from_utf8_lossy(&contents[at..annotation.offset]);
+        if color && !noise.is_empty() {
+            out.push_str(&format!("{}{}{}", DIM, noise, RESET));
+        } else {
+            out.push_str(&noise);
+        }
+
+        let text = String::from_utf8_lossy(&contents[annotation.offset..annotation.offset + annotation.len]);
+        match (color, annotation.enabled) {
+            (true, true) => out.push_str(&format!("{}{}{}", ENABLED, text, RESET)),
+            (true, false) => out.push_str(&format!("{}{}{}", DISABLED, text, RESET)),
+            (false, true) => out.push_str(&format!("[{}]", text)),
+            (false, false) => out.push_str(&format!("<{}>", text)),
+        }
+        at = annotation.offset + annotation.len;
+    }
+
+    let noise = String::from_utf8_lossy(&contents[at..]);
+    if color && !noise.is_empty() {
+        out.push_str(&format!("{}{}{}", DIM, noise, RESET));
+    } else {
+        out.push_str(&noise);
+    }
+    out
+}
@@ -0,0 +1,265 @@
+use std::io::{self, Read};
+
+use crate::number::Number;
+use crate::scanner::{Match, OperandPattern, Scanner, Syntax, MAX_ARITY};
+
+// Runs the instructions found in corrupted memory. Every instruction is an
+// entry in a registry: its syntax, which the scanner matches, and its effect
+// on the machine. New instructions are registered, not scanned for by hand.
+
+// Size of the reads in `run_reader`
+const CHUNK: usize = 64 * 1024;
+
+// What instructions act on
+pub struct Machine {
+    pub enabled: bool,
+    // Everything accumulated, enabled or not (part 1)
+    pub unconditional: Number,
+    // Only what was accumulated while enabled (part 2)
+    pub total: Number,
+    // Set once either total has needed more than 64 bits
+    pub overflowed: bool,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Machine {
+    pub fn new() -> Self {
+        Machine {
+            enabled: true, // Multiplications are enabled by default
+            unconditional: Number::zero(),
+            total: Number::zero(),
+            overflowed: false,
+        }
+    }
+
+    pub fn accumulate(&mut self, value: &Number) {
+        self.unconditional = &self.unconditional + value;
+        if self.enabled {
+            self.total = &self.total + value;
+        }
+        self.overflowed |= self.unconditional.is_big() || self.total.is_big();
+    }
+}
+
+pub type Effect = fn(&mut Machine, &[Number]);
+
+// One instruction as it ran
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub found: Match,
+    // Whether the machine was enabled when it ran
+    pub enabled: bool,
+    // What it added to the enabled-only total
+    pub contribution: Number,
+}
+
+pub struct Instruction {
+    pub syntax: Syntax,
+    pub effect: Effect,
+}
+
+impl Instruction {
+    pub fn new(name: &str, arity: usize, operands: OperandPattern, effect: Effect) -> Self {
+        Instruction {
+            syntax: Syntax {
+                name: name.to_string(),
+                arity,
+                operands,
+            },
+            effect,
+        }
+    }
+}
+
+// The puzzle's operands have 1 to 3 digits
+pub const PUZZLE_DIGITS: u32 = 3;
+
+pub fn mul(digits: u32) -> Instruction {
+    Instruction::new("mul", 2, OperandPattern::Unsigned { max_digits: digits }, |machine, operands| {
+        machine.accumulate(&(&operands[0] * &operands[1]))
+    })
+}
+
+pub fn enable() -> Instruction {
+    Instruction::new("do", 0, OperandPattern::Unsigned { max_digits: 1 }, |machine, _| machine.enabled = true)
+}
+
+pub fn disable() -> Instruction {
+    Instruction::new("don't", 0, OperandPattern::Unsigned { max_digits: 1 }, |machine, _| machine.enabled = false)
+}
+
+// Not part of the puzzle; examples of what can be registered alongside it
+pub fn extras(digits: u32) -> Vec<Instruction> {
+    let signed = OperandPattern::Signed { max_digits: digits };
+    vec![
+        Instruction::new("add", 2, signed, |machine, operands| {
+            machine.accumulate(&(&operands[0] + &operands[1]))
+        }),
+        Instruction::new("sub", 2, signed, |machine, operands| {
+            machine.accumulate(&(&operands[0] - &operands[1]))
+        }),
+        Instruction::new("neg", 1, signed, |machine, operands| machine.accumulate(&-&operands[0])),
+    ]
+}
+
+#[derive(Default)]
+pub struct Registry {
+    instructions: Vec<Instruction>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    // mul, do and don't
+    pub fn builtin() -> Self {
+        Registry::with_digits(PUZZLE_DIGITS)
+    }
+
+    // The same with mul taking operands of up to `digits` digits
+    pub fn with_digits(digits: u32) -> Self {
+        let mut registry = Registry::new();
+        for instruction in [mul(digits), enable(), disable()] {
+            registry.register(instruction).unwrap();
+        }
+        registry
+    }
+
+    pub fn register(&mut self, instruction: Instruction) -> Result<(), String> {
+        let syntax = &instruction.syntax;
+        if syntax.name.is_empty() || syntax.name.contains(['(', ')', ',']) {
+            return Err(format!("\"{}\" can't be an instruction name", syntax.name));
+        }
+        if syntax.arity > MAX_ARITY {
+            return Err(format!("{} takes {} operands, at most {} are allowed", syntax.name, syntax.arity, MAX_ARITY));
+        }
+        if syntax.operands.max_digits() == 0 {
+            return Err(format!("{} operands must allow at least 1 digit", syntax.name));
+        }
+        if self.instructions.iter().any(|i| i.syntax.name == syntax.name) {
+            return Err(format!("{} is already registered", syntax.name));
+        }
+
+        self.instructions.push(instruction);
+        Ok(())
+    }
+}
+
+pub struct Interpreter {
+    registry: Registry,
+    scanner: Scanner,
+    pub machine: Machine,
+    // How many times each instruction ran, in registry order
+    counts: Vec<usize>,
+}
+
+impl Interpreter {
+    pub fn new(registry: Registry) -> Self {
+        let syntaxes = registry.instructions.iter().map(|i| i.syntax.clone()).collect();
+        Interpreter {
+            counts: vec![0; registry.instructions.len()],
+            registry,
+            scanner: Scanner::new(syntaxes),
+            machine: Machine::new(),
+        }
+    }
+
+    // Runs every instruction in the next piece of memory
+    pub fn feed(&mut self, bytes: &[u8]) {
+        let (registry, machine, counts) = (&self.registry, &mut self.machine, &mut self.counts);
+        self.scanner.feed(bytes, |found| {
+            (registry.instructions[found.instruction].effect)(machine, found.operands());
+            counts[found.instruction] += 1;
+        });
+    }
+
+    // The same, reporting each instruction as it runs. Working out each run's
+    // contribution copies the total, so plain runs go through `feed` instead.
+    pub fn feed_with(&mut self, bytes: &[u8], mut on_run: impl FnMut(Run)) {
+        let (registry, machine, counts) = (&self.registry, &mut self.machine, &mut self.counts);
+        self.scanner.feed(bytes, |found| {
+            let (enabled, total) = (machine.enabled, machine.total.clone());
+            (registry.instructions[found.instruction].effect)(machine, found.operands());
+            counts[found.instruction] += 1;
+            let contribution = &machine.total - &total;
+            on_run(Run {
+                found,
+                enabled,
+                contribution,
+            });
+        });
+    }
+
+    pub fn name(&self, instruction: usize) -> &str {
+        &self.registry.instructions[instruction].syntax.name
+    }
+
+    // Runs a whole reader in fixed-size chunks
+    pub fn run_reader(&mut self, mut reader: impl Read) -> io::Result<()> {
+        let mut buffer = vec![0; CHUNK];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(n) => self.feed(&buffer[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // (name, times run) for every registered instruction
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        self.registry
+            .instructions
+            .iter()
+            .zip(&self.counts)
+            .map(|(instruction, &count)| (instruction.syntax.name.as_str(), count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY: &[u8] =
+        b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+add(-9,4)do()?mul(99999999999,99999999999)neg(12)sub(1,-1)mul(8,5))";
+
+    fn interpreter() -> Interpreter {
+        let mut registry = Registry::with_digits(12);
+        for instruction in extras(3) {
+            registry.register(instruction).unwrap();
+        }
+        Interpreter::new(registry)
+    }
+
+    #[test]
+    fn feed_matches_feed_with() {
+        let mut reported = interpreter();
+        let mut runs = Vec::new();
+        reported.feed_with(MEMORY, |run| runs.push(run));
+
+        // Split anywhere, feed ends up in the same place without reporting
+        for split in 0..MEMORY.len() {
+            let mut plain = interpreter();
+            plain.feed(&MEMORY[..split]);
+            plain.feed(&MEMORY[split..]);
+            assert_eq!(plain.machine.total.to_string(), reported.machine.total.to_string(), "split at {}", split);
+            assert_eq!(plain.machine.unconditional.to_string(), reported.machine.unconditional.to_string());
+            assert_eq!(plain.machine.overflowed, reported.machine.overflowed);
+            assert_eq!(plain.counts(), reported.counts());
+        }
+
+        // The contributions add up to the enabled-only total
+        let sum = runs.iter().fold(Number::zero(), |sum, run| &sum + &run.contribution);
+        assert_eq!(sum.to_string(), reported.machine.total.to_string());
+        assert!(reported.machine.overflowed);
+        assert_eq!(runs.iter().filter(|run| !run.enabled).count(), 3);
+    }
+}
@@ -0,0 +1,22 @@
+pub mod annotate;
+pub mod interpreter;
+pub mod number;
+pub mod scanner;
+
+use interpreter::{Interpreter, Registry};
+use number::Number;
+
+fn run(contents: &str) -> Interpreter {
+    let mut interpreter = Interpreter::new(Registry::builtin());
+    interpreter.feed(contents.as_bytes());
+    interpreter
+}
+
+pub fn part_1(contents: &str) -> Number {
+    // Every mul counts; do and don't only matter for part 2
+    run(contents).machine.unconditional
+}
+
+pub fn part_2(contents: &str) -> Number {
+    run(contents).machine.total
+}
@@ -1,63 +1,88 @@
-use regex::Regex;
-use std::fs;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal};
 
-fn part_1(filename: &str) {
-    // Read the file contents
-    let contents = fs::read_to_string(filename).unwrap();
+use day_3::annotate;
+use day_3::interpreter::{self, Interpreter, Machine, Registry};
 
-    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let input_file = args
+        .get(1)
+        .filter(|a| !a.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "../../input/day3/full.txt".to_string());
 
-    // Initialize sum
-    let mut total = 0;
+    // --stream scans the file in fixed-size chunks instead of reading it
+    // whole; --extras adds add, sub and neg; --counts shows how often each
+    // instruction ran
+    let stream = args.iter().any(|a| a == "--stream");
+    let extras = args.iter().any(|a| a == "--extras");
+    let counts = args.iter().any(|a| a == "--counts");
 
-    // Find all matches and process them
-    for cap in re.captures_iter(&contents) {
-        // Extract numbers from capture groups
-        let num1: i32 = cap[1].parse().unwrap();
-        let num2: i32 = cap[2].parse().unwrap();
+    // --digits <n> lets operands run to n digits instead of 3
+    let digits = match args.iter().position(|a| a == "--digits") {
+        Some(i) => args.get(i + 1).and_then(|n| n.parse().ok()).filter(|&n| n > 0).expect("--digits takes a positive number"),
+        None => interpreter::PUZZLE_DIGITS,
+    };
 
-        // Multiply numbers and add to total
-        let result = num1 * num2;
-        total += result;
+    // --annotate lists every instruction run; --render prints the dump with
+    // them highlighted, in colour on a terminal unless --no-color is given
+    let annotate = args.iter().any(|a| a == "--annotate");
+    let render = args.iter().any(|a| a == "--render");
+    if annotate || render {
+        let contents = fs::read(&input_file).expect("Failed to read input file");
+        let mut interpreter = Interpreter::new(registry(extras, digits));
+        let annotations = annotate::annotate(&mut interpreter, &contents);
+        if render {
+            let color = io::stdout().is_terminal() && !args.iter().any(|a| a == "--no-color");
+            println!("{}", annotate::render(&annotations, &contents, color));
+        }
+        if annotate {
+            print!("{}", annotate::list(&annotations));
+        }
+        print_totals(&interpreter.machine);
+        return;
     }
 
-    println!("Part 1: {}", total);
-}
-
-fn part_2(filename: &str) {
-    // Read the file contents
-    let contents = fs::read_to_string(filename).unwrap();
-
-    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)").unwrap();
+    if !(stream || extras || counts || digits != interpreter::PUZZLE_DIGITS) {
+        let contents = fs::read_to_string(&input_file).expect("Failed to read input file");
 
-    let mut total = 0;
-    let mut enabled = true;  // Multiplications are enabled by default
-
-    // Find and process all instructions in order
-    for cap in re.captures_iter(&contents) {
-        let instruction = cap.get(0).unwrap().as_str();
+        println!("Part 1: {}", day_3::part_1(&contents));
+        println!("Part 2: {}", day_3::part_2(&contents));
+        return;
+    }
 
-        if instruction == "do()" {
-            enabled = true;
-        } else if instruction == "don't()" {
-            enabled = false;
-        } else {
-            let num1: i32 = cap.get(1).unwrap().as_str().parse().unwrap();
-            let num2: i32 = cap.get(2).unwrap().as_str().parse().unwrap();
+    let mut interpreter = Interpreter::new(registry(extras, digits));
+    if stream {
+        let file = File::open(&input_file).expect("Failed to open input file");
+        interpreter.run_reader(file).expect("Failed to read input file");
+    } else {
+        interpreter.feed(&fs::read(&input_file).expect("Failed to read input file"));
+    }
 
-            if enabled {
-                let result = num1 * num2;
-                total += result;
-            }
+    print_totals(&interpreter.machine);
+    if counts {
+        for (name, count) in interpreter.counts() {
+            println!("  {}(): {}", name, count);
         }
     }
-
-    println!("Part 2: {}", total);
 }
 
-fn main() {
-    let input_file = "../../input/day3/full.txt";
+fn print_totals(machine: &Machine) {
+    println!("Part 1: {}", machine.unconditional);
+    println!("Part 2: {}", machine.total);
+    if machine.overflowed {
+        println!("(totals went past 64 bits and were finished with arbitrary precision)");
+    }
+}
 
-    part_1(input_file);
-    part_2(input_file);
+fn registry(extras: bool, digits: u32) -> Registry {
+    let mut registry = Registry::with_digits(digits);
+    if extras {
+        for instruction in interpreter::extras(digits) {
+            registry.register(instruction).unwrap();
+        }
+    }
+    registry
 }
@@ -0,0 +1,329 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// Integers for operands and totals. Arithmetic is checked 64-bit and moves to
+// arbitrary precision when a result doesn't fit, so nothing wraps or panics.
+// Results that fit in 64 bits again go back to the fast form.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Number {
+    Small(i64),
+    Big(BigInt),
+}
+
+impl Number {
+    pub fn zero() -> Self {
+        Number::Small(0)
+    }
+
+    // Past 64 bits
+    pub fn is_big(&self) -> bool {
+        matches!(self, Number::Big(_))
+    }
+
+    // self * 10 + digit, for reading operands a digit at a time
+    pub fn push_digit(&mut self, digit: u8) {
+        if let Number::Small(n) = self {
+            if let Some(next) = n.checked_mul(10).and_then(|n| n.checked_add(digit as i64)) {
+                *n = next;
+                return;
+            }
+        }
+        *self = &(&*self * &Number::Small(10)) + &Number::Small(digit as i64);
+    }
+
+    fn big(&self) -> BigInt {
+        match self {
+            Number::Small(n) => BigInt::from_i64(*n),
+            Number::Big(n) => n.clone(),
+        }
+    }
+
+    fn from_big(n: BigInt) -> Self {
+        match n.to_i64() {
+            Some(n) => Number::Small(n),
+            None => Number::Big(n),
+        }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(n: i64) -> Self {
+        Number::Small(n)
+    }
+}
+
+impl Add for &Number {
+    type Output = Number;
+
+    fn add(self, other: &Number) -> Number {
+        if let (Number::Small(a), Number::Small(b)) = (self, other) {
+            if let Some(sum) = a.checked_add(*b) {
+                return Number::Small(sum);
+            }
+        }
+        Number::from_big(self.big().add(&other.big()))
+    }
+}
+
+impl Sub for &Number {
+    type Output = Number;
+
+    fn sub(self, other: &Number) -> Number {
+        self + &-other
+    }
+}
+
+impl Mul for &Number {
+    type Output = Number;
+
+    fn mul(self, other: &Number) -> Number {
+        if let (Number::Small(a), Number::Small(b)) = (self, other) {
+            if let Some(product) = a.checked_mul(*b) {
+                return Number::Small(product);
+            }
+        }
+        Number::from_big(self.big().mul(&other.big()))
+    }
+}
+
+impl Neg for &Number {
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Small(n) => match n.checked_neg() {
+                Some(n) => Number::Small(n),
+                None => Number::Big(BigInt::from_i64(*n).neg()),
+            },
+            Number::Big(n) => Number::from_big(n.neg()),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Small(n) => f.pad(&n.to_string()),
+            Number::Big(n) => f.pad(&n.to_string()),
+        }
+    }
+}
+
+// Sign and magnitude, the magnitude in base 2^32 with the lowest limb first
+// and no high zero limbs. Zero is an empty magnitude and never negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn from_i64(n: i64) -> Self {
+        let mut magnitude = Vec::new();
+        let mut rest = n.unsigned_abs();
+        while rest > 0 {
+            magnitude.push(rest as u32);
+            rest >>= 32;
+        }
+        BigInt {
+            negative: n < 0,
+            magnitude,
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let value = self.magnitude.iter().rev().fold(0u64, |acc, &limb| (acc << 32) | limb as u64);
+        if self.negative {
+            0i64.checked_sub_unsigned(value)
+        } else {
+            i64::try_from(value).ok()
+        }
+    }
+
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    fn neg(&self) -> Self {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+
+    fn add(&self, other: &BigInt) -> Self {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add_magnitudes(&self.magnitude, &other.magnitude));
+        }
+        match compare_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::new(other.negative, sub_magnitudes(&other.magnitude, &self.magnitude)),
+            _ => BigInt::new(self.negative, sub_magnitudes(&self.magnitude, &other.magnitude)),
+        }
+    }
+
+    fn mul(&self, other: &BigInt) -> Self {
+        let mut product = vec![0u32; self.magnitude.len() + other.magnitude.len()];
+        for (i, &a) in self.magnitude.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.magnitude.iter().enumerate() {
+                let cell = product[i + j] as u64 + a as u64 * b as u64 + carry;
+                product[i + j] = cell as u32;
+                carry = cell >> 32;
+            }
+            product[i + other.magnitude.len()] = carry as u32;
+        }
+        BigInt::new(self.negative != other.negative, product)
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let cell = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(cell as u32);
+        carry = cell >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+// a - b where a >= b
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut cell = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if cell < 0 {
+            cell += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(cell as u32);
+    }
+    difference
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.magnitude.is_empty() {
+            return write!(f, "0");
+        }
+
+        // Peel off nine decimal digits at a time, lowest first
+        let mut rest = self.magnitude.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_empty() {
+            let mut remainder = 0u64;
+            for limb in rest.iter_mut().rev() {
+                let cell = (remainder << 32) | *limb as u64;
+                *limb = (cell / 1_000_000_000) as u32;
+                remainder = cell % 1_000_000_000;
+            }
+            while rest.last() == Some(&0) {
+                rest.pop();
+            }
+            chunks.push(remainder);
+        }
+
+        let mut out = String::new();
+        if self.negative {
+            out.push('-');
+        }
+        out.push_str(&chunks.pop().unwrap().to_string());
+        for chunk in chunks.iter().rev() {
+            out.push_str(&format!("{:09}", chunk));
+        }
+        write!(f, "{}", out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Values around the edges of i64, where the checked arithmetic gives up
+    fn samples() -> Vec<i64> {
+        let mut values = vec![0, 1, -1, 2, -2, 10, -10, 1 << 32, -(1 << 32), (1 << 32) - 1, 3_037_000_499, -3_037_000_500];
+        for edge in [i64::MAX, i64::MIN] {
+            for delta in 0..3 {
+                values.push(if edge > 0 { edge - delta } else { edge + delta });
+            }
+        }
+        values.extend([i64::MAX / 2, i64::MIN / 2, i64::MAX / 10, 999_999_999_999_999_999]);
+        values
+    }
+
+    // Checks a result against the exact value, including that it's only big when it has to be
+    fn check(number: &Number, expected: i128, what: &str) {
+        assert_eq!(number.to_string(), expected.to_string(), "{}", what);
+        assert_eq!(number.is_big(), i64::try_from(expected).is_err(), "{} is_big", what);
+    }
+
+    #[test]
+    fn matches_i128() {
+        let values = samples();
+        for &a in &values {
+            let x = Number::from(a);
+            check(&-&x, -(a as i128), &format!("-{}", a));
+            for &b in &values {
+                let y = Number::from(b);
+                check(&(&x + &y), a as i128 + b as i128, &format!("{} + {}", a, b));
+                check(&(&x - &y), a as i128 - b as i128, &format!("{} - {}", a, b));
+                check(&(&x * &y), a as i128 * b as i128, &format!("{} * {}", a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn big_results_come_back_small() {
+        let values = samples();
+        for &a in &values {
+            for &b in &values {
+                for &c in &values {
+                    // a * b is often big; adding c and taking a * b away again must land back on c
+                    let product = &Number::from(a) * &Number::from(b);
+                    let sum = &product + &Number::from(c);
+                    check(&sum, a as i128 * b as i128 + c as i128, &format!("{} * {} + {}", a, b, c));
+                    let back = &sum - &product;
+                    assert_eq!(back, Number::Small(c), "({} * {} + {}) - {} * {}", a, b, c, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn past_i128() {
+        // (2^63)^3 = 2^189, and its negation, printed exactly
+        let x = -&Number::from(i64::MIN);
+        let cube = &(&x * &x) * &x;
+        assert_eq!(cube.to_string(), "784637716923335095479473677900958302012794430558004314112");
+        assert_eq!((-&cube).to_string(), "-784637716923335095479473677900958302012794430558004314112");
+        assert_eq!(&cube - &cube, Number::zero());
+        assert_eq!(&(&cube + &Number::from(1)) - &cube, Number::Small(1));
+    }
+
+    #[test]
+    fn reads_digits() {
+        let mut number = Number::zero();
+        let digits = "98765432109876543210987654321";
+        for (i, d) in digits.bytes().enumerate() {
+            number.push_digit(d - b'0');
+            let expected: i128 = digits[..=i].parse().unwrap();
+            check(&number, expected, &digits[..=i]);
+        }
+        assert_eq!(format!("{:>32}", number), format!("{:>32}", digits));
+    }
+}
@@ -0,0 +1,338 @@
+use std::mem;
+
+use crate::number::Number;
+
+// Finds instructions of the form name(operand,operand,...) in corrupted
+// memory one byte at a time. Which names exist, how many operands each takes
+// and what an operand looks like come from the syntaxes the scanner is built
+// with. All it remembers between bytes is the instruction in progress, so
+// input can arrive in chunks of any size, split anywhere, and memory stays
+// bounded by the longest instruction.
+//
+// When a byte doesn't fit the instruction in progress, scanning starts again
+// one byte after where that instruction began, the way a regex looks for the
+// leftmost match. The bytes in between are replayed, since another name may
+// begin inside them.
+
+// Most operands any instruction can take
+pub const MAX_ARITY: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandPattern {
+    // 1 to max_digits decimal digits
+    Unsigned { max_digits: u32 },
+    // The same with an optional leading minus
+    Signed { max_digits: u32 },
+}
+
+impl OperandPattern {
+    pub fn max_digits(&self) -> u32 {
+        match *self {
+            OperandPattern::Unsigned { max_digits } | OperandPattern::Signed { max_digits } => max_digits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Syntax {
+    pub name: String,
+    pub arity: usize,
+    pub operands: OperandPattern,
+}
+
+// One instruction found: which syntax it matched, where, and its operands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub instruction: usize,
+    // Byte offset from the start of the input, and length in bytes
+    pub offset: u64,
+    pub len: usize,
+    arity: usize,
+    values: [Number; MAX_ARITY],
+}
+
+impl Match {
+    pub fn operands(&self) -> &[Number] {
+        &self.values[..self.arity]
+    }
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    // Matching a name; the bytes so far are in `pending`
+    Name,
+    // Reading operand `index` of instruction `id`; its digits so far are in `value`
+    Operand {
+        id: usize,
+        index: usize,
+        digits: u32,
+        negative: bool,
+    },
+    // A name with no operands, waiting for its ')'
+    Close { id: usize },
+}
+
+enum Step {
+    Continue,
+    Done(Match),
+    Fail,
+}
+
+pub struct Scanner {
+    syntaxes: Vec<Syntax>,
+    state: State,
+    // Bytes of the instruction in progress, and the offset of the first
+    pending: Vec<u8>,
+    start: u64,
+    // Offset of the next byte fed in
+    position: u64,
+    // Reused by `replay` so a failed match doesn't allocate
+    spare: Vec<u8>,
+    value: Number,
+    values: [Number; MAX_ARITY],
+}
+
+impl Scanner {
+    pub fn new(syntaxes: Vec<Syntax>) -> Self {
+        Scanner {
+            syntaxes,
+            state: State::Name,
+            pending: Vec::new(),
+            start: 0,
+            position: 0,
+            spare: Vec::new(),
+            value: Number::zero(),
+            values: std::array::from_fn(|_| Number::zero()),
+        }
+    }
+
+    // Scans the next piece of input, passing every completed instruction to `emit`
+    pub fn feed(&mut self, bytes: &[u8], mut emit: impl FnMut(Match)) {
+        for &byte in bytes {
+            self.push(byte, self.position, &mut emit);
+            self.position += 1;
+        }
+    }
+
+    fn push(&mut self, byte: u8, position: u64, emit: &mut impl FnMut(Match)) {
+        if self.pending.is_empty() {
+            self.start = position;
+        }
+        self.pending.push(byte);
+        match self.advance(byte) {
+            Step::Continue => {}
+            Step::Done(found) => {
+                self.reset();
+                emit(found);
+            }
+            Step::Fail => self.replay(emit),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = State::Name;
+        self.pending.clear();
+    }
+
+    // Nothing starts where the failed instruction did, so try again from the byte after
+    fn replay(&mut self, emit: &mut impl FnMut(Match)) {
+        if self.pending.len() == 1 {
+            self.reset();
+            return;
+        }
+
+        let mut bytes = mem::replace(&mut self.pending, mem::take(&mut self.spare));
+        let start = self.start;
+        self.reset();
+        for (i, &byte) in bytes.iter().enumerate().skip(1) {
+            self.push(byte, start + i as u64, emit);
+        }
+        bytes.clear();
+        self.spare = bytes;
+    }
+
+    fn advance(&mut self, byte: u8) -> Step {
+        match self.state {
+            State::Name => {
+                let word = &self.pending[..];
+                if let Some((&b'(', name)) = word.split_last() {
+                    if let Some(id) = self.syntaxes.iter().position(|s| s.name.as_bytes() == name) {
+                        self.state = match self.syntaxes[id].arity {
+                            0 => State::Close { id },
+                            _ => {
+                                self.value = Number::zero();
+                                State::Operand {
+                                    id,
+                                    index: 0,
+                                    digits: 0,
+                                    negative: false,
+                                }
+                            }
+                        };
+                        return Step::Continue;
+                    }
+                }
+
+                if self.syntaxes.iter().any(|s| s.name.as_bytes().starts_with(word)) {
+                    Step::Continue
+                } else {
+                    Step::Fail
+                }
+            }
+            State::Close { id } => match byte {
+                b')' => Step::Done(Match {
+                    instruction: id,
+                    offset: self.start,
+                    len: self.pending.len(),
+                    arity: 0,
+                    values: self.values.clone(),
+                }),
+                _ => Step::Fail,
+            },
+            State::Operand {
+                id,
+                index,
+                digits,
+                negative,
+            } => {
+                let syntax = &self.syntaxes[id];
+                match byte {
+                    b'0'..=b'9' if digits < syntax.operands.max_digits() => {
+                        self.value.push_digit(byte - b'0');
+                        self.state = State::Operand {
+                            id,
+                            index,
+                            digits: digits + 1,
+                            negative,
+                        };
+                        Step::Continue
+                    }
+                    b'-' if digits == 0 && !negative && matches!(syntax.operands, OperandPattern::Signed { .. }) => {
+                        self.state = State::Operand {
+                            id,
+                            index,
+                            digits,
+                            negative: true,
+                        };
+                        Step::Continue
+                    }
+                    b',' | b')' if digits > 0 => {
+                        let last = index + 1 == syntax.arity;
+                        if (byte == b')') != last {
+                            return Step::Fail;
+                        }
+
+                        let value = mem::replace(&mut self.value, Number::zero());
+                        self.values[index] = if negative { -&value } else { value };
+                        if last {
+                            Step::Done(Match {
+                                instruction: id,
+                                offset: self.start,
+                                len: self.pending.len(),
+                                arity: syntax.arity,
+                                values: self.values.clone(),
+                            })
+                        } else {
+                            self.state = State::Operand {
+                                id,
+                                index: index + 1,
+                                digits: 0,
+                                negative: false,
+                            };
+                            Step::Continue
+                        }
+                    }
+                    _ => Step::Fail,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syntaxes() -> Vec<Syntax> {
+        let syntax = |name: &str, arity, operands| Syntax {
+            name: name.to_string(),
+            arity,
+            operands,
+        };
+        let unsigned = OperandPattern::Unsigned { max_digits: 3 };
+        let signed = OperandPattern::Signed { max_digits: 3 };
+        vec![
+            syntax("mul", 2, unsigned),
+            syntax("do", 0, unsigned),
+            syntax("don't", 0, unsigned),
+            syntax("neg", 1, signed),
+        ]
+    }
+
+    // (name, offset, len, operands) of every match, feeding the input in the given pieces
+    fn scan(pieces: &[&[u8]]) -> Vec<(String, u64, usize, Vec<Number>)> {
+        let syntaxes = syntaxes();
+        let mut scanner = Scanner::new(syntaxes.clone());
+        let mut found = Vec::new();
+        for piece in pieces {
+            scanner.feed(piece, |m| {
+                let name = syntaxes[m.instruction].name.clone();
+                found.push((name, m.offset, m.len, m.operands().to_vec()));
+            });
+        }
+        found
+    }
+
+    // The same matches however the input is split: in two at every offset,
+    // and one byte at a time
+    fn check_splits(input: &str) -> Vec<(String, u64, usize, Vec<Number>)> {
+        let bytes = input.as_bytes();
+        let whole = scan(&[bytes]);
+        for at in 0..=bytes.len() {
+            let (head, tail) = bytes.split_at(at);
+            assert_eq!(scan(&[head, tail]), whole, "split at {} of {:?}", at, input);
+        }
+        let singles: Vec<&[u8]> = bytes.chunks(1).collect();
+        assert_eq!(scan(&singles), whole, "one byte at a time: {:?}", input);
+        whole
+    }
+
+    fn names(found: &[(String, u64, usize, Vec<Number>)]) -> Vec<&str> {
+        found.iter().map(|f| f.0.as_str()).collect()
+    }
+
+    #[test]
+    fn puzzle_example() {
+        let found = check_splits("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))");
+        assert_eq!(names(&found), ["mul", "don't", "mul", "mul", "do", "mul"]);
+        assert_eq!(found[0], ("mul".to_string(), 1, 8, vec![Number::from(2), Number::from(4)]));
+        assert_eq!(found[5].3, [Number::from(8), Number::from(5)]);
+    }
+
+    #[test]
+    fn replays_after_a_failed_name() {
+        // "do" fails at 'n' + 'e', and neg starts inside it
+        let found = check_splits("doneg(1)");
+        assert_eq!(found, [("neg".to_string(), 2, 6, vec![Number::from(1)])]);
+
+        let found = check_splits("mmul(1,1)dodo()don't(do()");
+        assert_eq!(names(&found), ["mul", "do", "do"]);
+        assert_eq!(found.iter().map(|f| f.1).collect::<Vec<_>>(), [1, 11, 21]);
+    }
+
+    #[test]
+    fn split_mid_token() {
+        let found = check_splits("don't()");
+        assert_eq!(names(&found), ["don't"]);
+
+        let found = scan(&[b"mul(1,2", b"3)"]);
+        assert_eq!(found, [("mul".to_string(), 0, 9, vec![Number::from(1), Number::from(23)])]);
+        check_splits("mul(1,23)");
+    }
+
+    #[test]
+    fn rejects_malformed_operands() {
+        let found = check_splits("mul(1234,5)mul(1,)mul(,1)mul(1,2,3)mul( 1,2)neg(--1)neg(-)neg(-12)mul(-1,2)");
+        assert_eq!(found, [("neg".to_string(), 58, 8, vec![Number::from(-12)])]);
+    }
+}
@@ -0,0 +1,24 @@
+// The letter grid, addressed as (row, column) from the top left. Rows can be
+// ragged; cells past the end of a short row simply don't exist.
+
+pub struct Grid {
+    cells: Vec<Vec<char>>,
+    pub rows: usize,
+    // Width of the longest row
+    pub cols: usize,
+}
+
+impl Grid {
+    pub fn parse(contents: &str) -> Self {
+        let cells: Vec<Vec<char>> = contents.lines().map(|line| line.chars().collect()).collect();
+        Grid {
+            rows: cells.len(),
+            cols: cells.iter().map(|row| row.len()).max().unwrap_or(0),
+            cells,
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<char> {
+        self.cells.get(row)?.get(col).copied()
+    }
+}
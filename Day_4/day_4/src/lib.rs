@@ -0,0 +1,18 @@
+pub mod grid;
+pub mod render;
+pub mod search;
+pub mod stencil;
+
+use grid::Grid;
+use stencil::{Stencil, Template};
+
+pub fn part_1(contents: &str) -> i32 {
+    // Every XMAS, in any of the eight directions
+    search::find(&Grid::parse(contents), &["XMAS"]).len() as i32
+}
+
+pub fn part_2(contents: &str) -> i32 {
+    // Every X-MAS: the template in any rotation or reflection
+    let x_mas = Stencil::new(Template::parse(stencil::X_MAS).unwrap(), true);
+    x_mas.find(&Grid::parse(contents)).len() as i32
+}
@@ -1,122 +1,99 @@
-use std::fs::read_to_string;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
 
-fn part_1(filename: &str) {
-    // Read the file
-    let contents = match read_to_string(filename) {
-        Ok(contents) => contents,
-        Err(e) => {
-            println!("Error reading file: {}", e);
+use day_4::grid::Grid;
+use day_4::render;
+use day_4::search;
+use day_4::stencil::{self, Stencil, Template};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let input_file = args
+        .get(1)
+        .filter(|a| !a.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "../../input/day4/full.txt".to_string());
+    let contents = fs::read_to_string(&input_file).expect("Failed to read input file");
+    let grid = Grid::parse(&contents);
+
+    // --show prints the grid with only matched letters left in, highlighted
+    // on a terminal unless --no-color is given; --json exports the matches
+    let show = args.iter().any(|a| a == "--show");
+    let json = args.iter().any(|a| a == "--json");
+    let color = io::stdout().is_terminal() && !args.iter().any(|a| a == "--no-color");
+
+    // --words <w1,w2,...> lists every occurrence of each word instead
+    if let Some(i) = args.iter().position(|a| a == "--words") {
+        let list = args.get(i + 1).expect("--words takes a comma-separated list of words");
+        let words: Vec<&str> = list.split(',').filter(|w| !w.is_empty()).collect();
+        let matches = search::find(&grid, &words);
+
+        if show {
+            print!("{}", render::render(&grid, &render::covered(&matches, None, &[]), color));
+        }
+        if json {
+            print!("{}", render::to_json(&words, &matches, None, &[]));
+        }
+        if show || json {
             return;
         }
-    };
-
-    // Convert input to 2D grid
-    let grid: Vec<Vec<char>> = contents
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect();
-
-    let directions = [
-        (-1, -1), (-1, 0), (-1, 1),  // up-left, up, up-right
-        (0, -1),           (0, 1),    // left, right
-        (1, -1),  (1, 0),  (1, 1),    // down-left, down, down-right
-    ];
-
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut count = 0;
-
-    for i in 0..rows {
-        for j in 0..cols {
-            for &(dx, dy) in &directions {
-                if is_xmas_at_position(&grid, i, j, dx, dy) {
-                    count += 1;
-                }
-            }
+        for m in &matches {
+            println!(
+                "{} ({},{}) -> ({},{}) {}",
+                words[m.word], m.start.0, m.start.1, m.end.0, m.end.1, m.direction.name()
+            );
         }
+        for (index, word) in words.iter().enumerate() {
+            println!("{}: {}", word, matches.iter().filter(|m| m.word == index).count());
+        }
+        return;
     }
 
-    println!("Part 1: {}", count);
-}
-
-fn is_xmas_at_position(grid: &[Vec<char>], start_x: usize, start_y: usize, dx: i32, dy: i32) -> bool {
-    let target = ['X', 'M', 'A', 'S'];
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
-
-    // Check each character position
-    for step in 0..4 {
-        // Calculate new position
-        let x = start_x as i32 + dx * step;
-        let y = start_y as i32 + dy * step;
-
-        // Check bounds
-        if x < 0 || x >= rows || y < 0 || y >= cols {
-            return false;
+    // --stencil <template file> lists every placement of a 2D template, in
+    // any rotation or reflection unless --exact is given
+    if let Some(i) = args.iter().position(|a| a == "--stencil") {
+        let path = args.get(i + 1).expect("--stencil takes a template file");
+        let text = fs::read_to_string(path).expect("Failed to read template file");
+        let template = Template::parse(&text).unwrap_or_else(|e| panic!("Bad template: {}", e));
+        let stencil = Stencil::new(template, !args.iter().any(|a| a == "--exact"));
+        let matches = stencil.find(&grid);
+
+        if show {
+            print!("{}", render::render(&grid, &render::covered(&[], Some(&stencil), &matches), color));
         }
-
-        // Check character match
-        if grid[x as usize][y as usize] != target[step as usize] {
-            return false;
+        if json {
+            print!("{}", render::to_json(&[], &[], Some(&stencil), &matches));
         }
-    }
-
-    true
-}
-
-fn part_2(filename: &str) {
-    // Read the file
-    let contents = match read_to_string(filename) {
-        Ok(contents) => contents,
-        Err(e) => {
-            println!("Error reading file: {}", e);
+        if show || json {
             return;
         }
-    };
-
-    // Convert input to 2D grid
-    let grid: Vec<Vec<char>> = contents
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect();
-
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut count = 0;
-
-    for i in 1..rows-1 {  // Skip first and last rows since we need diagonal space
-        for j in 1..cols-1 {  // Skip first and last columns
-            if grid[i][j] == 'A' {
-                // Check all possible X-MAS patterns centered at this 'A'
-                if is_x_mas_at_position(&grid, i, j) {
-                    count += 1;
-                }
-            }
+        for m in &matches {
+            println!("({},{}) variant {}", m.origin.0, m.origin.1, m.variant);
         }
+        println!("{} matches, {} variants", matches.len(), stencil.variants.len());
+        return;
     }
 
-    println!("Part 2: {}", count);
-}
-
-fn is_x_mas_at_position(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    // Get the four diagonal positions around 'A'
-    let top_left = grid[x-1][y-1];
-    let top_right = grid[x-1][y+1];
-    let bottom_left = grid[x+1][y-1];
-    let bottom_right = grid[x+1][y+1];
-
-    let is_valid_mas = |start: char, end: char| {
-        (start == 'M' && end == 'S') || (start == 'S' && end == 'M')
-    };
-
-    let diagonal1 = is_valid_mas(top_left, bottom_right);
-    let diagonal2 = is_valid_mas(top_right, bottom_left);
-
-    diagonal1 && diagonal2
-}
+    // Without a query, the two puzzle parts: XMAS and the X-MAS stencil
+    if show || json {
+        let words = ["XMAS"];
+        let xmas = search::find(&grid, &words);
+        let x_mas = Stencil::new(Template::parse(stencil::X_MAS).unwrap(), true);
+        let crosses = x_mas.find(&grid);
+
+        if show {
+            println!("Part 1: {}", xmas.len());
+            println!("{}", render::render(&grid, &render::covered(&xmas, None, &[]), color));
+            println!("Part 2: {}", crosses.len());
+            print!("{}", render::render(&grid, &render::covered(&[], Some(&x_mas), &crosses), color));
+        }
+        if json {
+            print!("{}", render::to_json(&words, &xmas, Some(&x_mas), &crosses));
+        }
+        return;
+    }
 
-fn main() {
-    let input_file = "../../input/day4/full.txt";
-    part_1(input_file);
-    part_2(input_file);
+    println!("Part 1: {}", day_4::part_1(&contents));
+    println!("Part 2: {}", day_4::part_2(&contents));
 }
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use crate::grid::Grid;
+use crate::search::WordMatch;
+use crate::stencil::{Stencil, StencilMatch};
+
+// Shows where matches are: the grid with only the letters that belong to a
+// match left in, like the puzzle's illustrations, and a JSON export.
+
+const HIGHLIGHT: &str = "\x1b[1;32m";
+const RESET: &str = "\x1b[0m";
+
+// Every cell covered by at least one match
+pub fn covered(words: &[WordMatch], stencil: Option<&Stencil>, placed: &[StencilMatch]) -> HashSet<(usize, usize)> {
+    let mut cells: HashSet<(usize, usize)> = words.iter().flat_map(|m| m.cells()).collect();
+    if let Some(stencil) = stencil {
+        cells.extend(placed.iter().flat_map(|m| stencil.cells(m)));
+    }
+    cells
+}
+
+// The grid with every cell not in `cells` replaced by '.', and the ones that
+// are in bold green when `color` is set
+pub fn render(grid: &Grid, cells: &HashSet<(usize, usize)>, color: bool) -> String {
+    let mut out = String::with_capacity(grid.rows * (grid.cols + 1));
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            match grid.get(row, col) {
+                Some(c) if cells.contains(&(row, col)) => {
+                    if color {
+                        out.push_str(&format!("{}{}{}", HIGHLIGHT, c, RESET));
+                    } else {
+                        out.push(c);
+                    }
+                }
+                Some(_) => out.push('.'),
+                None => {}
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn json_list(values: impl Iterator<Item = String>) -> String {
+    format!("[{}]", values.collect::<Vec<_>>().join(","))
+}
+
+fn json_cell((row, col): (usize, usize)) -> String {
+    format!("[{},{}]", row, col)
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// {"words": [...], "stencils": [...]} with cells as [row, column]. Word
+// matches carry the word, start, end and direction; stencil matches the
+// variant and origin. Both list every cell they cover.
+pub fn to_json(
+    words: &[&str],
+    word_matches: &[WordMatch],
+    stencil: Option<&Stencil>,
+    placed: &[StencilMatch],
+) -> String {
+    let word_entries: Vec<String> = word_matches
+        .iter()
+        .map(|m| {
+            format!(
+                "    {{\"word\":{},\"start\":{},\"end\":{},\"direction\":\"{}\",\"cells\":{}}}",
+                json_string(words[m.word]),
+                json_cell(m.start),
+                json_cell(m.end),
+                m.direction.name(),
+                json_list(m.cells().map(json_cell))
+            )
+        })
+        .collect();
+    let stencil_entries: Vec<String> = match stencil {
+        Some(stencil) => placed
+            .iter()
+            .map(|m| {
+                format!(
+                    "    {{\"variant\":{},\"origin\":{},\"cells\":{}}}",
+                    m.variant,
+                    json_cell(m.origin),
+                    json_list(stencil.cells(m).into_iter().map(json_cell))
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let section = |entries: &[String]| match entries.is_empty() {
+        true => "[]".to_string(),
+        false => format!("[\n{}\n  ]", entries.join(",\n")),
+    };
+    format!(
+        "{{\n  \"words\": {},\n  \"stencils\": {}\n}}\n",
+        section(&word_entries),
+        section(&stencil_entries)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search;
+    use crate::stencil::{Template, X_MAS};
+
+    const EXAMPLE: &str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\n\
+                           XXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX\n";
+
+    fn x_mas() -> Stencil {
+        Stencil::new(Template::parse(X_MAS).unwrap(), true)
+    }
+
+    // The puzzle's own pictures of where the matches are
+    #[test]
+    fn shows_the_example_matches() {
+        let grid = Grid::parse(EXAMPLE);
+        let words = search::find(&grid, &["XMAS"]);
+        assert_eq!(
+            render(&grid, &covered(&words, None, &[]), false),
+            "....XXMAS.\n.SAMXMS...\n...S..A...\n..A.A.MS.X\nXMASAMX.MM\n\
+             X.....XA.A\nS.S.S.S.SS\n.A.A.A.A.A\n..M.M.M.MM\n.X.X.XMASX\n"
+        );
+
+        let stencil = x_mas();
+        let crosses = stencil.find(&grid);
+        assert_eq!(
+            render(&grid, &covered(&[], Some(&stencil), &crosses), false),
+            ".M.S......\n..A..MSMS.\n.M.S.MAA..\n..A.ASMSM.\n.M.S.M....\n\
+             ..........\nS.S.S.S.S.\n.A.A.A.A..\nM.M.M.M.M.\n..........\n"
+        );
+    }
+
+    #[test]
+    fn highlights_in_color() {
+        let grid = Grid::parse("XMAS\nXMAX\n");
+        let words = search::find(&grid, &["XMAS"]);
+        let green = |c: char| format!("{}{}{}", HIGHLIGHT, c, RESET);
+        let first_row: String = "XMAS".chars().map(green).collect();
+        assert_eq!(render(&grid, &covered(&words, None, &[]), true), format!("{}\n....\n", first_row));
+    }
+
+    #[test]
+    fn json_shape() {
+        let words = ["XMAS", "SAM"];
+        let word_matches = search::find(&Grid::parse("XMAS\n"), &words);
+        let stencil = x_mas();
+        let placed = stencil.find(&Grid::parse("M.S\n.A.\nM.S\n"));
+        assert_eq!(
+            to_json(&words, &word_matches, Some(&stencil), &placed),
+            [
+                "{",
+                "  \"words\": [",
+                "    {\"word\":\"XMAS\",\"start\":[0,0],\"end\":[0,3],\"direction\":\"right\",\"cells\":[[0,0],[0,1],[0,2],[0,3]]},",
+                "    {\"word\":\"SAM\",\"start\":[0,3],\"end\":[0,1],\"direction\":\"left\",\"cells\":[[0,3],[0,2],[0,1]]}",
+                "  ],",
+                "  \"stencils\": [",
+                "    {\"variant\":0,\"origin\":[0,0],\"cells\":[[0,0],[0,2],[1,1],[2,0],[2,2]]}",
+                "  ]",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(to_json(&[], &[], None, &[]), "{\n  \"words\": [],\n  \"stencils\": []\n}\n");
+        assert_eq!(json_string("a\"b\\\n"), "\"a\\\"b\\\\\\u000a\"");
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::grid::Grid;
+
+// Finds any number of words in the grid at once. Every row, column and
+// diagonal is walked once each way, and an Aho-Corasick automaton built from
+// the words reports every word ending at each cell, so the cost doesn't grow
+// with the number of words or their overlap.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Right,
+    Left,
+    Down,
+    Up,
+    DownRight,
+    UpLeft,
+    DownLeft,
+    UpRight,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::Right,
+        Direction::Left,
+        Direction::Down,
+        Direction::Up,
+        Direction::DownRight,
+        Direction::UpLeft,
+        Direction::DownLeft,
+        Direction::UpRight,
+    ];
+
+    // (row, column) change per letter
+    pub fn step(self) -> (isize, isize) {
+        match self {
+            Direction::Right => (0, 1),
+            Direction::Left => (0, -1),
+            Direction::Down => (1, 0),
+            Direction::Up => (-1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::DownLeft => (1, -1),
+            Direction::UpRight => (-1, 1),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::Right => "right",
+            Direction::Left => "left",
+            Direction::Down => "down",
+            Direction::Up => "up",
+            Direction::DownRight => "down-right",
+            Direction::UpLeft => "up-left",
+            Direction::DownLeft => "down-left",
+            Direction::UpRight => "up-right",
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+            Direction::Down => Direction::Up,
+            Direction::Up => Direction::Down,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::UpRight => Direction::DownLeft,
+        }
+    }
+}
+
+// One word found: its index in the query, its first and last letters, and
+// which way it reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    pub word: usize,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub direction: Direction,
+}
+
+impl WordMatch {
+    // Every cell of the word, first letter first
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (dr, dc) = self.direction.step();
+        let len = self.start.0.abs_diff(self.end.0).max(self.start.1.abs_diff(self.end.1)) + 1;
+        let start = self.start;
+        (0..len as isize).map(move |i| ((start.0 as isize + dr * i) as usize, (start.1 as isize + dc * i) as usize))
+    }
+}
+
+struct Node {
+    next: HashMap<char, usize>,
+    // Longest proper suffix of this node's prefix that is also in the trie
+    fail: usize,
+    // Words ending here, including through the fail links
+    outputs: Vec<usize>,
+}
+
+impl Node {
+    fn new() -> Self {
+        Node {
+            next: HashMap::new(),
+            fail: 0,
+            outputs: Vec::new(),
+        }
+    }
+}
+
+pub struct Automaton {
+    nodes: Vec<Node>,
+    // Length in letters of each word
+    lengths: Vec<usize>,
+}
+
+impl Automaton {
+    // Empty words are never matched
+    pub fn new(words: &[&str]) -> Self {
+        let mut nodes = vec![Node::new()];
+        for (index, word) in words.iter().enumerate() {
+            if word.is_empty() {
+                continue;
+            }
+            let mut node = 0;
+            for c in word.chars() {
+                node = match nodes[node].next.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::new());
+                        let next = nodes.len() - 1;
+                        nodes[node].next.insert(c, next);
+                        next
+                    }
+                };
+            }
+            nodes[node].outputs.push(index);
+        }
+
+        // Fail links breadth first, so a node's are set before its children's
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(char, usize)> = nodes[node].next.iter().map(|(&c, &child)| (c, child)).collect();
+            for (c, child) in children {
+                let mut fail = nodes[node].fail;
+                while fail != 0 && !nodes[fail].next.contains_key(&c) {
+                    fail = nodes[fail].fail;
+                }
+                let fail = nodes[fail].next.get(&c).copied().filter(|&f| f != child).unwrap_or(0);
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        Automaton {
+            nodes,
+            lengths: words.iter().map(|w| w.chars().count()).collect(),
+        }
+    }
+
+    fn step(&self, mut node: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[node].next.get(&c) {
+                return next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    // Runs along one line of cells read in `direction`
+    fn scan(&self, grid: &Grid, line: &[(usize, usize)], direction: Direction, matches: &mut Vec<WordMatch>) {
+        let mut node = 0;
+        for (i, &(row, col)) in line.iter().enumerate() {
+            // A missing cell (past a short row) breaks the line
+            let Some(c) = grid.get(row, col) else {
+                node = 0;
+                continue;
+            };
+            node = self.step(node, c);
+            for &word in &self.nodes[node].outputs {
+                matches.push(WordMatch {
+                    word,
+                    start: line[i + 1 - self.lengths[word]],
+                    end: (row, col),
+                    direction,
+                });
+            }
+        }
+    }
+}
+
+// Every row, column and diagonal, each with the direction it reads in
+fn lines(grid: &Grid) -> Vec<(Vec<(usize, usize)>, Direction)> {
+    let (rows, cols) = (grid.rows, grid.cols);
+    let walk = |mut row: usize, mut col: usize, direction: Direction| {
+        let (dr, dc) = direction.step();
+        let mut line = Vec::new();
+        while row < rows && col < cols {
+            line.push((row, col));
+            match (row.checked_add_signed(dr), col.checked_add_signed(dc)) {
+                (Some(r), Some(c)) => (row, col) = (r, c),
+                _ => break,
+            }
+        }
+        (line, direction)
+    };
+
+    let mut lines = Vec::new();
+    lines.extend((0..rows).map(|r| walk(r, 0, Direction::Right)));
+    lines.extend((0..cols).map(|c| walk(0, c, Direction::Down)));
+    lines.extend((0..cols).map(|c| walk(0, c, Direction::DownRight)));
+    lines.extend((1..rows).map(|r| walk(r, 0, Direction::DownRight)));
+    lines.extend((0..cols).map(|c| walk(0, c, Direction::DownLeft)));
+    lines.extend((1..rows).map(|r| walk(r, cols.saturating_sub(1), Direction::DownLeft)));
+    lines
+}
+
+// Every occurrence of every word, in all eight directions, ordered by start
+// cell. A word that reads the same both ways (or a single letter) is found
+// once per direction it reads in.
+pub fn find(grid: &Grid, words: &[&str]) -> Vec<WordMatch> {
+    let automaton = Automaton::new(words);
+    let mut matches = Vec::new();
+    for (mut line, direction) in lines(grid) {
+        automaton.scan(grid, &line, direction, &mut matches);
+        line.reverse();
+        automaton.scan(grid, &line, direction.opposite(), &mut matches);
+    }
+    matches.sort_by_key(|m| (m.start, m.direction, m.word));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every (word, start, end, direction) found by trying each word from each cell in each direction
+    fn brute_force(grid: &Grid, words: &[&str]) -> Vec<WordMatch> {
+        let mut matches = Vec::new();
+        for row in 0..grid.rows {
+            for col in 0..grid.cols {
+                for direction in Direction::ALL {
+                    let (dr, dc) = direction.step();
+                    for (word, text) in words.iter().enumerate() {
+                        let letters: Vec<char> = text.chars().collect();
+                        let cell = |i: usize| {
+                            let r = row.checked_add_signed(dr * i as isize)?;
+                            let c = col.checked_add_signed(dc * i as isize)?;
+                            Some((r, c))
+                        };
+                        let fits = !letters.is_empty()
+                            && letters
+                                .iter()
+                                .enumerate()
+                                .all(|(i, &l)| cell(i).and_then(|(r, c)| grid.get(r, c)) == Some(l));
+                        if fits {
+                            matches.push(WordMatch {
+                                word,
+                                start: (row, col),
+                                end: cell(letters.len() - 1).unwrap(),
+                                direction,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        matches.sort_by_key(|m| (m.start, m.direction, m.word));
+        matches
+    }
+
+    fn grids(seed: u64, letters: &[char], count: usize) -> Vec<String> {
+        let mut state = seed;
+        let mut below = |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n) as usize
+        };
+        (0..count)
+            .map(|i| {
+                let rows = 1 + below(9);
+                let width = 1 + below(9);
+                (0..rows)
+                    .map(|_| {
+                        // Every other grid has short rows
+                        let len = if i % 2 == 0 { width } else { below(width as u64 + 1) };
+                        (0..len).map(|_| letters[below(letters.len() as u64)]).collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+
+    fn check(words: &[&str], letters: &[char], seed: u64) {
+        for text in grids(seed, letters, 300) {
+            let grid = Grid::parse(&text);
+            assert_eq!(find(&grid, words), brute_force(&grid, words), "{:?} in\n{}", words, text);
+        }
+    }
+
+    #[test]
+    fn puzzle_example() {
+        let text = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
+        let grid = Grid::parse(text);
+        assert_eq!(find(&grid, &["XMAS"]).len(), 18);
+        assert_eq!(find(&grid, &["XMAS"]), brute_force(&grid, &["XMAS"]));
+    }
+
+    #[test]
+    fn overlapping_words_and_fail_links() {
+        // Words that are prefixes, suffixes and infixes of each other, so
+        // matches are only found through fail links and shared outputs
+        check(&["ABAB", "BAB", "AB", "ABABAC", "BA", "C"], &['A', 'B', 'C'], 1);
+        check(&["XMAS", "MAS", "AS", "SAMX", "XMASAMX"], &['X', 'M', 'A', 'S'], 2);
+    }
+
+    #[test]
+    fn palindromes_and_single_letters() {
+        // Found once per direction they read in, as the brute force does
+        check(&["ABA", "A", "AA", "BAAB"], &['A', 'B'], 3);
+        let grid = Grid::parse("A");
+        assert_eq!(find(&grid, &["A"]).len(), 8);
+    }
+
+    #[test]
+    fn duplicate_and_empty_words() {
+        check(&["AB", "", "AB", "B"], &['A', 'B'], 4);
+        assert!(find(&Grid::parse("AB\nBA"), &[""]).is_empty());
+    }
+
+    #[test]
+    fn short_rows_break_lines() {
+        // The column through (0, 2) stops at the short second row
+        let grid = Grid::parse("ABC\nA\nABC");
+        let matches = find(&grid, &["CXC", "CC"]);
+        assert!(matches.is_empty());
+        let matches = find(&grid, &["AAA"]);
+        assert_eq!(matches.len(), 2);
+    }
+}
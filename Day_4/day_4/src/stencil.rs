@@ -0,0 +1,238 @@
+use crate::grid::Grid;
+
+// Finds 2D shapes in the grid. A template is a small block of text where
+// each letter has to be in the grid at that spot and '.' matches anything.
+// A stencil is a template plus, optionally, every rotation and reflection of
+// it, with duplicates dropped so a symmetric shape isn't counted twice.
+
+pub const WILDCARD: char = '.';
+
+// Part 2's X-MAS: two MAS crossing on the A
+pub const X_MAS: &str = "M.S\n.A.\nM.S";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    // None for a wildcard
+    cells: Vec<Vec<Option<char>>>,
+}
+
+impl Template {
+    // Short rows are padded with wildcards, and rows and columns that are all
+    // wildcards around the edges are trimmed, so the top-left cell of a match
+    // is always inside the grid
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut cells: Vec<Vec<Option<char>>> = text
+            .lines()
+            .map(|line| {
+                let mut row: Vec<Option<char>> = line.chars().map(|c| Some(c).filter(|&c| c != WILDCARD)).collect();
+                row.resize(width, None);
+                row
+            })
+            .collect();
+
+        cells.retain(|row| row.iter().any(Option::is_some));
+        if cells.is_empty() {
+            return Err("template has no letters".to_string());
+        }
+        let first = (0..width).find(|&c| cells.iter().any(|row| row[c].is_some())).unwrap();
+        let last = (0..width).rfind(|&c| cells.iter().any(|row| row[c].is_some())).unwrap();
+        for row in &mut cells {
+            row.truncate(last + 1);
+            row.drain(..first);
+        }
+        Ok(Template { cells })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    // Turned a quarter clockwise
+    pub fn rotate(&self) -> Self {
+        Template {
+            cells: (0..self.cols())
+                .map(|c| (0..self.rows()).rev().map(|r| self.cells[r][c]).collect())
+                .collect(),
+        }
+    }
+
+    // Mirrored left to right
+    pub fn reflect(&self) -> Self {
+        Template {
+            cells: self.cells.iter().map(|row| row.iter().rev().copied().collect()).collect(),
+        }
+    }
+
+    // (row, column, letter) of every non-wildcard cell
+    pub fn letters(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| row.iter().enumerate().filter_map(move |(c, cell)| cell.map(|ch| (r, c, ch))))
+    }
+
+    fn matches_at(&self, grid: &Grid, row: usize, col: usize) -> bool {
+        self.letters().all(|(r, c, ch)| grid.get(row + r, col + c) == Some(ch))
+    }
+}
+
+// One placement of a stencil: which variant, and the grid cell under its
+// top-left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StencilMatch {
+    pub variant: usize,
+    pub origin: (usize, usize),
+}
+
+pub struct Stencil {
+    pub variants: Vec<Template>,
+}
+
+impl Stencil {
+    // With `transform`, all eight rotations and reflections that differ
+    pub fn new(template: Template, transform: bool) -> Self {
+        let mut variants = vec![template];
+        if transform {
+            for i in 0..7 {
+                let next = if i == 3 { variants[0].reflect() } else { variants.last().unwrap().rotate() };
+                variants.push(next);
+            }
+            let mut unique: Vec<Template> = Vec::new();
+            for variant in variants {
+                if !unique.contains(&variant) {
+                    unique.push(variant);
+                }
+            }
+            variants = unique;
+        }
+        Stencil { variants }
+    }
+
+    // Every placement of every variant, ordered by origin. Two different
+    // variants fitting the same spot are two matches.
+    pub fn find(&self, grid: &Grid) -> Vec<StencilMatch> {
+        let mut matches = Vec::new();
+        for row in 0..grid.rows {
+            for col in 0..grid.cols {
+                for (variant, template) in self.variants.iter().enumerate() {
+                    if template.matches_at(grid, row, col) {
+                        matches.push(StencilMatch {
+                            variant,
+                            origin: (row, col),
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    // The grid cells under the letters of a match
+    pub fn cells(&self, found: &StencilMatch) -> Vec<(usize, usize)> {
+        let (row, col) = found.origin;
+        self.variants[found.variant].letters().map(|(r, c, _)| (row + r, col + c)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\n\
+                           XXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX\n";
+
+    // Part 2 as it was solved before stencils: an A with MAS or SAM on both diagonals
+    fn crossed_mas(grid: &Grid) -> usize {
+        let mut count = 0;
+        for row in 1..grid.rows.saturating_sub(1) {
+            for col in 1..grid.cols.saturating_sub(1) {
+                let at = |r: usize, c: usize| grid.get(r, c).unwrap_or('.');
+                let mas = |a: char, b: char| (a == 'M' && b == 'S') || (a == 'S' && b == 'M');
+                if at(row, col) == 'A'
+                    && mas(at(row - 1, col - 1), at(row + 1, col + 1))
+                    && mas(at(row - 1, col + 1), at(row + 1, col - 1))
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn stencil(text: &str, transform: bool) -> Stencil {
+        Stencil::new(Template::parse(text).unwrap(), transform)
+    }
+
+    #[test]
+    fn x_mas_reproduces_part_2() {
+        let x_mas = stencil(X_MAS, true);
+        // Its reflections are among its rotations
+        assert_eq!(x_mas.variants.len(), 4);
+        assert_eq!(x_mas.find(&Grid::parse(EXAMPLE)).len(), 9);
+        assert_eq!(crate::part_2(EXAMPLE), 9);
+
+        // Small random grids over the letters that matter
+        let mut state: u64 = 0x0004_0049;
+        for _ in 0..200 {
+            let text: String = (0..6)
+                .map(|_| {
+                    let mut line: String = (0..7)
+                        .map(|_| {
+                            state ^= state << 13;
+                            state ^= state >> 7;
+                            state ^= state << 17;
+                            ['M', 'A', 'S', 'X'][(state % 4) as usize]
+                        })
+                        .collect();
+                    line.push('\n');
+                    line
+                })
+                .collect();
+            let grid = Grid::parse(&text);
+            assert_eq!(x_mas.find(&grid).len(), crossed_mas(&grid), "{}", text);
+        }
+    }
+
+    #[test]
+    fn symmetric_templates_count_once() {
+        // Same under every rotation and reflection
+        let square = stencil("XX\nXX", true);
+        assert_eq!(square.variants.len(), 1);
+        assert_eq!(square.find(&Grid::parse("XXX\nXXX\nXXX\n")).len(), 4);
+
+        let plus = stencil(".A.\nAAA\n.A.", true);
+        assert_eq!(plus.variants.len(), 1);
+
+        // A word reads the same reflected, so only its rotations differ
+        let word = stencil("MAS", true);
+        assert_eq!(word.variants.len(), 4);
+        assert_eq!(word.find(&Grid::parse("MAS\nA.A\nSAM\n")).len(), 4);
+
+        // An L has all eight
+        assert_eq!(stencil("X.\nXX", true).variants.len(), 4);
+        assert_eq!(stencil("X..\nXXX", true).variants.len(), 8);
+
+        // --exact keeps the template as given
+        assert_eq!(stencil(X_MAS, false).variants.len(), 1);
+    }
+
+    #[test]
+    fn parses_and_transforms() {
+        let template = Template::parse("...\n.M.\n..S\n....").unwrap();
+        assert_eq!((template.rows(), template.cols()), (2, 2));
+        assert_eq!(template.letters().collect::<Vec<_>>(), [(0, 0, 'M'), (1, 1, 'S')]);
+        assert!(Template::parse("..\n..").is_err());
+        assert!(Template::parse("").is_err());
+
+        let l = Template::parse("X..\nXXX").unwrap();
+        assert_eq!(l.rotate(), Template::parse("XX\nX.\nX.").unwrap());
+        assert_eq!(l.rotate().rotate().rotate().rotate(), l);
+        assert_eq!(l.reflect(), Template::parse("..X\nXXX").unwrap());
+        assert_eq!(l.reflect().reflect(), l);
+    }
+}
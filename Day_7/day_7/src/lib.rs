@@ -0,0 +1,85 @@
+#[derive(Debug)]
+struct Equation {
+    test_value: i64,
+    numbers: Vec<i64>,
+}
+
+fn evaluate(numbers: &[i64], operators: &[char]) -> i64 {
+    let mut result = numbers[0];
+    for i in 0..operators.len() {
+        match operators[i] {
+            '+' => result += numbers[i + 1],
+            '*' => result *= numbers[i + 1],
+            '|' => {
+                // Convert both numbers to strings, concatenate, then parse back to i64
+                let combined = format!("{}{}", result, numbers[i + 1]);
+                result = combined.parse().unwrap();
+            }
+            _ => panic!("Invalid operator"),
+        }
+    }
+    result
+}
+
+fn try_combinations(eq: &Equation, include_concat: bool) -> bool {
+    let operator_positions = eq.numbers.len() - 1;
+    let num_operators: i32 = if include_concat { 3 } else { 2 };
+    let total_combinations = num_operators.pow(operator_positions as u32);
+
+    for i in 0..total_combinations {
+        let mut operators = Vec::new();
+        for j in 0..operator_positions {
+            // Convert number to base-3 (or base-2 for part 1) for different operators
+            let operator = match (i / num_operators.pow(j as u32)) % num_operators {
+                0 => '+',
+                1 => '*',
+                2 => '|',  // Only used in part 2
+                _ => panic!("Invalid operator index"),
+            };
+            operators.push(operator);
+        }
+
+        if evaluate(&eq.numbers, &operators) == eq.test_value {
+            return true;
+        }
+    }
+    false
+}
+
+fn parse_line(line: &str) -> Equation {
+    let parts: Vec<&str> = line.split(':').collect();
+    let test_value = parts[0].trim().parse().unwrap();
+
+    let numbers: Vec<i64> = parts[1]
+        .split_whitespace()
+        .map(|n| n.parse().unwrap())
+        .collect();
+
+    Equation {
+        test_value,
+        numbers,
+    }
+}
+
+fn parse_equations(input: &str) -> Vec<Equation> {
+    input.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_line)
+        .collect()
+}
+
+pub fn part_1(input: &str) -> i64 {
+    let equations = parse_equations(input);
+    equations.iter()
+        .filter(|eq| try_combinations(eq, false))
+        .map(|eq| eq.test_value)
+        .sum()
+}
+
+pub fn part_2(input: &str) -> i64 {
+    let equations = parse_equations(input);
+    equations.iter()
+        .filter(|eq| try_combinations(eq, true))
+        .map(|eq| eq.test_value)
+        .sum()
+}
@@ -0,0 +1,76 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Matches layout::LEGACY_YEAR, whose inputs sit directly under input/
+const LEGACY_YEAR: u32 = 2024;
+
+fn embed_year(year: u32, input_dir: &Path, entries: &mut String) {
+    println!("cargo:rerun-if-changed={}", input_dir.display());
+
+    for day in 1..=25 {
+        for kind in ["sample", "full"] {
+            let path = input_dir.join(format!("day{}", day)).join(format!("{}.txt", kind));
+            if path.is_file() {
+                entries.push_str(&format!(
+                    "    ({}, {}, {:?}, include_str!({:?})),\n",
+                    year, day, kind, path
+                ));
+            }
+        }
+    }
+}
+
+// Keeps include/aoc.h in step with src/ffi.rs. The header is committed so C
+// tooling doesn't need cbindgen, so this only runs with the c-header feature.
+#[cfg(feature = "c-header")]
+fn generate_header(manifest_dir: &Path) {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(manifest_dir.join("cbindgen.toml")).unwrap();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(manifest_dir.join("src").join("ffi.rs"))
+        .generate()
+        .expect("failed to generate include/aoc.h")
+        .write_to_file(manifest_dir.join("include").join("aoc.h"));
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+    let out_file = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "c-header")]
+    generate_header(&manifest_dir);
+
+    // Only files that exist get embedded; everything else is read at runtime
+    let mut entries = String::new();
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        embed_year(LEGACY_YEAR, &root.join("input"), &mut entries);
+
+        // Later years keep their inputs under <year>/input. Watching the repo root
+        // would rescan every target dir, so a brand new year folder needs build.rs
+        // touched (or a clean build) before it gets embedded.
+        let mut years: Vec<u32> = fs::read_dir(root)
+            .unwrap()
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+            .filter(|&year| year != LEGACY_YEAR)
+            .collect();
+        years.sort();
+
+        for year in years {
+            embed_year(year, &root.join(year.to_string()).join("input"), &mut entries);
+        }
+    }
+
+    let contents = format!(
+        "static EMBEDDED: &[(u32, u32, &str, &str)] = &[\n{}];\n",
+        entries
+    );
+    fs::write(out_file, contents).unwrap();
+}
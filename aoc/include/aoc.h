@@ -0,0 +1,84 @@
+/* Generated from src/ffi.rs by cbindgen; do not edit by hand. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Bumped whenever a signature or status code below changes meaning
+#define AOC_ABI_VERSION 1
+
+// Bumped whenever the plugin entry points or `AocPluginInfo` change
+#define AOC_PLUGIN_ABI_VERSION 1
+
+// Bit for part 1 in `AocPluginInfo::parts`
+#define AOC_PART_1 1
+
+// Bit for part 2 in `AocPluginInfo::parts`
+#define AOC_PART_2 2
+
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  AOC_STATUS_INVALID_PART = 2,
+  // The input buffer is not valid UTF-8
+  AOC_STATUS_INVALID_INPUT = 3,
+  // The solver panicked, usually on malformed input
+  AOC_STATUS_SOLVER_FAILED = 4,
+  AOC_STATUS_NULL_ARGUMENT = 5,
+} AocStatus;
+
+typedef struct AocPluginInfo {
+  // NUL-terminated name used to select this implementation
+  const char *name;
+  uint32_t year;
+  uint32_t day;
+  // `AOC_PART_1` and/or `AOC_PART_2`
+  uint32_t parts;
+} AocPluginInfo;
+
+// `uint32_t aoc_plugin_abi_version(void)`: must return `AOC_PLUGIN_ABI_VERSION`
+typedef uint32_t (*AocPluginAbiVersionFn)(void);
+
+// `const AocPluginInfo *aoc_plugin_info(void)`: the pointer must stay valid
+// while the library is loaded
+typedef const struct AocPluginInfo *(*AocPluginInfoFn)(void);
+
+// `int32_t aoc_plugin_solve(part, input, input_len, out)`: same contract as
+// `aoc_solve` without the day, returning an `AocStatus` value
+typedef int32_t (*AocPluginSolveFn)(uint32_t part,
+                                    const uint8_t *input,
+                                    size_t input_len,
+                                    char **out);
+
+// `void aoc_plugin_free_string(char *s)`: releases strings from `aoc_plugin_solve`
+typedef void (*AocPluginFreeStringFn)(char *s);
+
+uint32_t aoc_abi_version(void);
+
+// Solves one part of a 2024 day.
+//
+// On `AOC_STATUS_OK` `*out` holds the answer; any other status except
+// `AOC_STATUS_NULL_ARGUMENT` leaves an error message there instead. Either way
+// the string must be released with `aoc_free_string`.
+//
+// # Safety
+// `input` must point to `input_len` readable bytes (it may be null when
+// `input_len` is 0) and `out` must be a valid pointer to write to.
+enum AocStatus aoc_solve(uint32_t day,
+                         uint32_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         char **out);
+
+// Frees a string returned by `aoc_solve`. Passing null is a no-op.
+//
+// # Safety
+// `s` must come from `aoc_solve` and must not be freed twice.
+void aoc_free_string(char *s);
+
+#endif  /* AOC_H */
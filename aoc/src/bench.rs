@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, AllocStats};
+use crate::inputs;
+use crate::layout::Layout;
+use crate::solvers::{self, Solver};
+
+fn alloc_column(stats: Option<AllocStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "  allocs {:>8}  total {:>10}  peak {:>10}",
+            stats.allocations,
+            alloc::format_bytes(stats.bytes),
+            alloc::format_bytes(stats.peak)
+        ),
+        None => String::new(),
+    }
+}
+
+fn bench_solver(layout: &Layout, solver: &Solver, kind: &str, iterations: u32) {
+    let (contents, load_stats) = alloc::measure(|| inputs::load(layout, solver.day, kind));
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(e) => {
+            println!("Day {}: failed to read input: {}", solver.day, e);
+            return;
+        }
+    };
+
+    println!("Day {}", solver.day);
+    if load_stats.is_some() {
+        println!("  {:<44}{}", "input", alloc_column(load_stats));
+    }
+
+    for part in [1, 2] {
+        // Allocation counts come from the first run; the rest only feed the timings
+        let start = Instant::now();
+        let (outcome, stats) = alloc::measure(|| solver.try_solve(part, &contents));
+        let mut times = vec![start.elapsed()];
+
+        // A part that fails isn't timed, and the other parts and days still run
+        let answer = match outcome {
+            Ok(answer) => answer,
+            Err(e) => {
+                println!("  Part {}: failed: {}", part, e);
+                continue;
+            }
+        };
+
+        for _ in 1..iterations {
+            let start = Instant::now();
+            let _ = solver.try_solve(part, &contents);
+            times.push(start.elapsed());
+        }
+
+        let min = times.iter().min().unwrap();
+        let mean = times.iter().sum::<Duration>() / times.len() as u32;
+        let label = format!("Part {}: {}", part, answer);
+        println!(
+            "  {:<24}min {:>9.2?}  mean {:>9.2?}{}",
+            label, min, mean, alloc_column(stats)
+        );
+    }
+}
+
+pub fn bench(layout: &Layout, day: Option<u32>, kind: &str, iterations: u32) {
+    if solvers::for_year(layout.year).is_empty() {
+        println!("No {} solvers are linked into aoc, nothing to bench", layout.year);
+        return;
+    }
+
+    if !alloc::enabled() {
+        println!("(build with --features alloc-stats for allocation counts)");
+    }
+
+    match day {
+        Some(day) => match solvers::find(layout.year, day) {
+            Some(solver) => bench_solver(layout, solver, kind, iterations),
+            None => println!("Day {} has no solver", day),
+        },
+        None => {
+            for solver in solvers::for_year(layout.year) {
+                bench_solver(layout, solver, kind, iterations);
+            }
+        }
+    }
+}
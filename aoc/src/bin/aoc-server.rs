@@ -0,0 +1,56 @@
+use std::env;
+use std::process;
+use std::time::Duration;
+
+use aoc::server::{Server, ServerConfig};
+
+fn usage_error() -> ! {
+    println!("Usage: aoc-server [--port <port>] [--max-concurrent <n>] [--max-detached <n>] [--max-connections <n>] [--timeout <seconds>]");
+    process::exit(1);
+}
+
+fn flag_value<T: std::str::FromStr>(args: &[String], flag: &str) -> Option<T> {
+    let pos = args.iter().position(|a| a == flag)?;
+    match args.get(pos + 1).map(|v| v.parse()) {
+        Some(Ok(value)) => Some(value),
+        _ => usage_error(),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut config = ServerConfig::default();
+
+    if let Some(port) = flag_value(&args, "--port") {
+        config.port = port;
+    }
+    if let Some(max) = flag_value::<usize>(&args, "--max-concurrent") {
+        config.max_concurrent = max.max(1);
+    }
+    if let Some(max) = flag_value(&args, "--max-detached") {
+        config.max_detached = max;
+    }
+    if let Some(max) = flag_value::<usize>(&args, "--max-connections") {
+        config.max_connections = max.max(1);
+    }
+    if let Some(secs) = flag_value(&args, "--timeout") {
+        config.timeout = Duration::from_secs(secs);
+    }
+
+    let server = match Server::bind(config.clone()) {
+        Ok(server) => server,
+        Err(e) => {
+            println!("Failed to bind port {}: {}", config.port, e);
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "Listening on http://{} (max {} concurrent, {:?} timeout)",
+        server.local_addr(),
+        config.max_concurrent,
+        config.timeout
+    );
+    println!("POST /day/{{n}}/part/{{p}} with the puzzle input as the body");
+    server.run();
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// Helpers for driving a day through its own binary rather than in-process
+
+pub fn binary_path(day_dir: &Path, day: u32) -> PathBuf {
+    day_dir.join("target").join("debug").join(format!("day_{}", day))
+}
+
+pub fn build(day_dir: &Path) -> bool {
+    let status = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .current_dir(day_dir)
+        .status();
+
+    match status {
+        Ok(status) => status.success(),
+        Err(e) => {
+            println!("Failed to run cargo: {}", e);
+            false
+        }
+    }
+}
+
+// Collects the "Part N: answer" lines a day prints
+pub fn run_day(day_dir: &Path, day: u32, input_file: &Path) -> HashMap<u32, String> {
+    let binary = binary_path(day_dir, day);
+    let output = Command::new(&binary)
+        .arg(input_file)
+        .current_dir(day_dir)
+        .stdin(Stdio::null())
+        .output();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            println!("Failed to run {}: {}", binary.display(), e);
+            return HashMap::new();
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("Part ")?;
+            let (part, answer) = rest.split_once(':')?;
+            Some((part.trim().parse().ok()?, answer.trim().to_string()))
+        })
+        .collect()
+}
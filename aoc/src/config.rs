@@ -0,0 +1,36 @@
+use std::fs;
+use std::path::Path;
+
+use crate::layout::LEGACY_YEAR;
+
+// Settings read from aoc.toml at the repo root. Only flat `key = value` lines
+// are understood, which is all the file needs.
+pub struct Config {
+    pub year: u32,
+}
+
+impl Config {
+    pub fn load(root: &Path) -> Config {
+        let mut config = Config { year: LEGACY_YEAR };
+
+        let contents = match fs::read_to_string(root.join("aoc.toml")) {
+            Ok(contents) => contents,
+            Err(_) => return config,
+        };
+
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            if key.trim() == "year" {
+                if let Ok(year) = value.trim().parse() {
+                    config.year = year;
+                }
+            }
+        }
+
+        config
+    }
+}
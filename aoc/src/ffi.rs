@@ -0,0 +1,129 @@
+// C interface for the linked solvers, built into libaoc as a cdylib.
+// include/aoc.h is generated from this file; see build.rs.
+use std::ffi::{c_char, CString};
+use std::slice;
+
+use crate::solvers;
+
+/// Bumped whenever a signature or status code below changes meaning
+pub const AOC_ABI_VERSION: u32 = 1;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    UnknownDay = 1,
+    InvalidPart = 2,
+    /// The input buffer is not valid UTF-8
+    InvalidInput = 3,
+    /// The solver panicked, usually on malformed input
+    SolverFailed = 4,
+    NullArgument = 5,
+}
+
+fn to_c_string(text: &str) -> *mut c_char {
+    // Interior NULs can only come from panic messages; drop them rather than fail
+    CString::new(text.replace('\0', "")).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_abi_version() -> u32 {
+    AOC_ABI_VERSION
+}
+
+/// Solves one part of a 2024 day.
+///
+/// On `AOC_STATUS_OK` `*out` holds the answer; any other status except
+/// `AOC_STATUS_NULL_ARGUMENT` leaves an error message there instead. Either way
+/// the string must be released with `aoc_free_string`.
+///
+/// # Safety
+/// `input` must point to `input_len` readable bytes (it may be null when
+/// `input_len` is 0) and `out` must be a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out: *mut *mut c_char,
+) -> AocStatus {
+    if out.is_null() || (input.is_null() && input_len > 0) {
+        return AocStatus::NullArgument;
+    }
+
+    let (status, text) = match solve(day, part, input, input_len) {
+        Ok(answer) => (AocStatus::Ok, answer),
+        Err((status, message)) => (status, message),
+    };
+    *out = to_c_string(&text);
+    status
+}
+
+unsafe fn solve(day: u32, part: u32, input: *const u8, input_len: usize) -> Result<String, (AocStatus, String)> {
+    let Some(solver) = solvers::find(solvers::LINKED_YEAR, day) else {
+        return Err((AocStatus::UnknownDay, format!("no solver for day {}", day)));
+    };
+    if part != 1 && part != 2 {
+        return Err((AocStatus::InvalidPart, format!("part must be 1 or 2, got {}", part)));
+    }
+
+    let bytes = if input_len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let input = std::str::from_utf8(bytes)
+        .map_err(|e| (AocStatus::InvalidInput, format!("input is not UTF-8: {}", e)))?;
+
+    solver
+        .try_solve(part, input)
+        .map_err(|e| (AocStatus::SolverFailed, e))
+}
+
+/// Frees a string returned by `aoc_solve`. Passing null is a no-op.
+///
+/// # Safety
+/// `s` must come from `aoc_solve` and must not be freed twice.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+// Plugins are shared libraries dropped into plugins/ that export the four
+// entry points below; the runner loads them at startup (see plugins.rs).
+
+/// Bumped whenever the plugin entry points or `AocPluginInfo` change
+pub const AOC_PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Bit for part 1 in `AocPluginInfo::parts`
+pub const AOC_PART_1: u32 = 1;
+/// Bit for part 2 in `AocPluginInfo::parts`
+pub const AOC_PART_2: u32 = 2;
+
+#[repr(C)]
+pub struct AocPluginInfo {
+    /// NUL-terminated name used to select this implementation
+    pub name: *const c_char,
+    pub year: u32,
+    pub day: u32,
+    /// `AOC_PART_1` and/or `AOC_PART_2`
+    pub parts: u32,
+}
+
+/// `uint32_t aoc_plugin_abi_version(void)`: must return `AOC_PLUGIN_ABI_VERSION`
+pub type AocPluginAbiVersionFn = unsafe extern "C" fn() -> u32;
+
+/// `const AocPluginInfo *aoc_plugin_info(void)`: the pointer must stay valid
+/// while the library is loaded
+pub type AocPluginInfoFn = unsafe extern "C" fn() -> *const AocPluginInfo;
+
+/// `int32_t aoc_plugin_solve(part, input, input_len, out)`: same contract as
+/// `aoc_solve` without the day, returning an `AocStatus` value
+pub type AocPluginSolveFn =
+    unsafe extern "C" fn(part: u32, input: *const u8, input_len: usize, out: *mut *mut c_char) -> i32;
+
+/// `void aoc_plugin_free_string(char *s)`: releases strings from `aoc_plugin_solve`
+pub type AocPluginFreeStringFn = unsafe extern "C" fn(s: *mut c_char);
@@ -0,0 +1,430 @@
+// Synthetic 2024 inputs for `aoc stress`. At scale 1 each generator produces
+// roughly the size of a real puzzle input; scale n multiplies the amount of
+// input (lines, grid cells or characters) by about n.
+
+// Small xorshift generator so stress inputs are reproducible without a rand dependency
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // Uniform in lo..hi
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next_u64() % (hi - lo)
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.range(0, 100) < percent
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() as u64) as usize]
+    }
+}
+
+// Grids grow in both directions, so the side grows with the square root of the scale
+fn side(base: usize, scale: u32) -> usize {
+    (base as f64 * (scale as f64).sqrt()).round() as usize
+}
+
+fn day_1(rng: &mut Rng, scale: u32) -> Option<String> {
+    let lines = 1000 * scale as usize;
+    Some(
+        (0..lines)
+            .map(|_| format!("{}   {}\n", rng.range(10000, 100000), rng.range(10000, 100000)))
+            .collect(),
+    )
+}
+
+fn day_2(rng: &mut Rng, scale: u32) -> Option<String> {
+    let mut out = String::new();
+    for _ in 0..1000 * scale {
+        let len = rng.range(5, 9);
+        let increasing = rng.chance(50);
+        let mut level = rng.range(20, 80) as i64;
+        let mut levels = Vec::new();
+        for _ in 0..len {
+            levels.push(level.to_string());
+            // Mostly valid steps, with the odd bad one for the dampener to deal with
+            let step = if rng.chance(90) { rng.range(1, 4) as i64 } else { rng.range(0, 6) as i64 };
+            level += if increasing { step } else { -step };
+        }
+        out.push_str(&levels.join(" "));
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn day_3(rng: &mut Rng, scale: u32) -> Option<String> {
+    const JUNK: &[&str] = &["mul[", "mul(4*", "do_not", "what()", ")", "select()", "mul ( 2 , 4 )", "%&", "from()", "?"];
+    let target = 18000 * scale as usize;
+    let mut out = String::new();
+    while out.len() < target {
+        match rng.range(0, 10) {
+            0..=3 => out.push_str(&format!("mul({},{})", rng.range(1, 1000), rng.range(1, 1000))),
+            4 => out.push_str("do()"),
+            5 => out.push_str("don't()"),
+            _ => out.push_str(rng.pick(JUNK)),
+        }
+        if rng.chance(2) {
+            out.push('\n');
+        }
+    }
+    out.push('\n');
+    Some(out)
+}
+
+fn day_4(rng: &mut Rng, scale: u32) -> Option<String> {
+    let n = side(140, scale);
+    let mut out = String::with_capacity(n * (n + 1));
+    for _ in 0..n {
+        for _ in 0..n {
+            out.push(rng.pick(&['X', 'M', 'A', 'S']));
+        }
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn day_5(rng: &mut Rng, scale: u32) -> Option<String> {
+    // Every pair of pages is ordered, like the real input
+    let mut pages: Vec<u64> = (11..60).collect();
+    for i in (1..pages.len()).rev() {
+        let j = rng.range(0, i as u64 + 1) as usize;
+        pages.swap(i, j);
+    }
+
+    let mut out = String::new();
+    for i in 0..pages.len() {
+        for j in i + 1..pages.len() {
+            out.push_str(&format!("{}|{}\n", pages[i], pages[j]));
+        }
+    }
+    out.push('\n');
+
+    for _ in 0..200 * scale {
+        let len = 2 * rng.range(2, 12) as usize + 1;
+        let mut update = pages.clone();
+        for i in 0..len {
+            let j = rng.range(i as u64, update.len() as u64) as usize;
+            update.swap(i, j);
+        }
+        let update = &mut update[..len];
+        // Some already follow the rules, so part 1 has updates to count
+        if rng.chance(30) {
+            update.sort_by_key(|p| pages.iter().position(|q| q == p));
+        }
+        let update: Vec<String> = update.iter().map(|p| p.to_string()).collect();
+        out.push_str(&update.join(","));
+        out.push('\n');
+    }
+    Some(out)
+}
+
+// Walks the guard by day 6's rules. If it ends up in a loop, returns the
+// obstacle it had just turned at a second time.
+fn guard_loop(grid: &[Vec<u8>], start: (usize, usize)) -> Option<(usize, usize)> {
+    let (rows, cols) = (grid.len() as i64, grid[0].len() as i64);
+    let mut turned = vec![0u8; grid.len() * grid[0].len()];
+    let (mut r, mut c) = (start.0 as i64, start.1 as i64);
+    let mut dir = 0;
+    let deltas = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+    loop {
+        let (dr, dc) = deltas[dir];
+        let (nr, nc) = (r + dr, c + dc);
+        if nr < 0 || nr >= rows || nc < 0 || nc >= cols {
+            return None;
+        }
+        if grid[nr as usize][nc as usize] == b'#' {
+            let cell = (r * cols + c) as usize;
+            if turned[cell] & (1 << dir) != 0 {
+                return Some((nr as usize, nc as usize));
+            }
+            turned[cell] |= 1 << dir;
+            dir = (dir + 1) % 4;
+        } else {
+            (r, c) = (nr, nc);
+        }
+    }
+}
+
+fn day_6(rng: &mut Rng, scale: u32) -> Option<String> {
+    let n = side(130, scale);
+    let start = (n / 2, n / 2);
+
+    let mut grid: Vec<Vec<u8>> = (0..n)
+        .map(|_| (0..n).map(|_| if rng.chance(5) { b'#' } else { b'.' }).collect())
+        .collect();
+    grid[start.0][start.1] = b'.';
+
+    // Part 1 expects the guard to leave; large maps nearly always trap it, so
+    // knock out obstacles that close a loop until it gets out
+    while let Some((r, c)) = guard_loop(&grid, start) {
+        grid[r][c] = b'.';
+    }
+
+    grid[start.0][start.1] = b'^';
+    let mut out = String::with_capacity(n * (n + 1));
+    for row in grid {
+        out.push_str(std::str::from_utf8(&row).unwrap());
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn day_7(rng: &mut Rng, scale: u32) -> Option<String> {
+    let mut out = String::new();
+    for _ in 0..850 * scale {
+        // Single digits keep every concatenation and product inside an i64
+        let count = rng.range(3, 13);
+        let numbers: Vec<i64> = (0..count).map(|_| rng.range(1, 10) as i64).collect();
+
+        // About half the equations are solvable
+        let mut value = numbers[0];
+        for &n in &numbers[1..] {
+            value = match rng.range(0, 3) {
+                0 => value + n,
+                1 => value * n,
+                _ => format!("{}{}", value, n).parse().unwrap(),
+            };
+        }
+        if rng.chance(50) {
+            value += 1;
+        }
+
+        let numbers: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+        out.push_str(&format!("{}: {}\n", value, numbers.join(" ")));
+    }
+    Some(out)
+}
+
+fn day_8(rng: &mut Rng, scale: u32) -> Option<String> {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let n = side(50, scale);
+    let mut out = String::with_capacity(n * (n + 1));
+    for _ in 0..n {
+        for _ in 0..n {
+            out.push(if rng.chance(8) { rng.pick(FREQUENCIES) as char } else { '.' });
+        }
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn day_9(rng: &mut Rng, scale: u32) -> Option<String> {
+    // The map has to end on a file, so the length is odd
+    let len = 20000 * scale as usize + 1;
+    let mut out: String = (0..len)
+        .map(|i| {
+            let lo = if i % 2 == 0 { 1 } else { 0 };
+            char::from(b'0' + rng.range(lo, 10) as u8)
+        })
+        .collect();
+    out.push('\n');
+    Some(out)
+}
+
+fn day_10(rng: &mut Rng, scale: u32) -> Option<String> {
+    // Diagonal slopes with some noise give plenty of hiking trails
+    let n = side(45, scale);
+    let mut out = String::with_capacity(n * (n + 1));
+    for r in 0..n {
+        for c in 0..n {
+            let height = if rng.chance(80) { (r + c) % 10 } else { rng.range(0, 10) as usize };
+            out.push(char::from(b'0' + height as u8));
+        }
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn day_11(rng: &mut Rng, scale: u32) -> Option<String> {
+    let stones: Vec<String> = (0..8 * scale).map(|_| rng.range(0, 10_000_000).to_string()).collect();
+    Some(stones.join(" ") + "\n")
+}
+
+fn day_12(rng: &mut Rng, scale: u32) -> Option<String> {
+    // Blocky regions with ragged edges
+    let n = side(140, scale);
+    let block = 10;
+    let blocks = n.div_ceil(block) + 1;
+    let letters: Vec<u8> = (0..blocks * blocks).map(|_| rng.range(0, 26) as u8 + b'A').collect();
+
+    let mut out = String::with_capacity(n * (n + 1));
+    for r in 0..n {
+        for c in 0..n {
+            let (mut br, mut bc) = (r / block, c / block);
+            if rng.chance(15) {
+                br += 1;
+            }
+            if rng.chance(15) {
+                bc += 1;
+            }
+            out.push(letters[br * blocks + bc] as char);
+        }
+        out.push('\n');
+    }
+    Some(out)
+}
+
+// Part 2 moves every prize this far along both axes
+const OFFSET: i128 = 10_000_000_000_000;
+
+fn day_13(rng: &mut Rng, scale: u32) -> Option<String> {
+    let mut out = String::new();
+    for _ in 0..320 * scale {
+        let (mut a, mut b) = ((rng.range(10, 100), rng.range(10, 100)), (rng.range(10, 100), rng.range(10, 100)));
+        let prize = match rng.range(0, 3) {
+            // Reachable in part 1: a whole number of presses of each button
+            0 => {
+                let (presses_a, presses_b) = (rng.range(1, 101), rng.range(1, 101));
+                (presses_a * a.0 + presses_b * b.0, presses_a * a.1 + presses_b * b.1)
+            }
+            // Reachable in part 2: A leans along X and B along Y so both press
+            // counts come out positive for a target far along the diagonal,
+            // rounded down to whole presses and the offset taken back off
+            1 => {
+                if a.0 <= a.1 {
+                    a = (a.1 + 1, a.0);
+                }
+                if b.1 <= b.0 {
+                    b = (b.1, b.0 + 1);
+                }
+                let (ax, ay, bx, by) = (a.0 as i128, a.1 as i128, b.0 as i128, b.1 as i128);
+                let target = (OFFSET + rng.range(1000, 20000) as i128, OFFSET + rng.range(1000, 20000) as i128);
+                let det = ax * by - ay * bx;
+                let presses_a = (target.0 * by - target.1 * bx) / det;
+                let presses_b = (target.1 * ax - target.0 * ay) / det;
+                (
+                    (presses_a * ax + presses_b * bx - OFFSET) as u64,
+                    (presses_a * ay + presses_b * by - OFFSET) as u64,
+                )
+            }
+            _ => (rng.range(1000, 20000), rng.range(1000, 20000)),
+        };
+        out.push_str(&format!("Button A: X+{}, Y+{}\n", a.0, a.1));
+        out.push_str(&format!("Button B: X+{}, Y+{}\n", b.0, b.1));
+        out.push_str(&format!("Prize: X={}, Y={}\n\n", prize.0, prize.1));
+    }
+    Some(out)
+}
+
+fn day_14(rng: &mut Rng, scale: u32) -> Option<String> {
+    let (width, height) = (day_14::WIDTH as u64, day_14::HEIGHT as u64);
+    let robots = 500 * scale as u64;
+
+    // Part 2 needs a moment where no robots overlap, so there can't be more
+    // robots than tiles; beyond half the grid the scale isn't meaningful
+    if robots > width * height / 2 {
+        return None;
+    }
+
+    // Pick distinct tiles at a random time and wind the robots back to time 0
+    let moment = rng.range(0, width * height);
+    let mut taken = vec![false; (width * height) as usize];
+    let mut out = String::new();
+    for _ in 0..robots {
+        let (x, y) = loop {
+            let (x, y) = (rng.range(0, width), rng.range(0, height));
+            if !taken[(y * width + x) as usize] {
+                taken[(y * width + x) as usize] = true;
+                break (x, y);
+            }
+        };
+        let (vx, vy) = (rng.range(0, 200) as i64 - 100, rng.range(0, 200) as i64 - 100);
+        let x0 = (x as i64 - vx * moment as i64).rem_euclid(width as i64);
+        let y0 = (y as i64 - vy * moment as i64).rem_euclid(height as i64);
+        out.push_str(&format!("p={},{} v={},{}\n", x0, y0, vx, vy));
+    }
+    Some(out)
+}
+
+fn day_15(rng: &mut Rng, scale: u32) -> Option<String> {
+    let n = side(50, scale);
+    let mut out = String::with_capacity(n * (n + 1));
+    for r in 0..n {
+        for c in 0..n {
+            let ch = if r == 0 || c == 0 || r == n - 1 || c == n - 1 {
+                '#'
+            } else if (r, c) == (n / 2, n / 2) {
+                '@'
+            } else {
+                match rng.range(0, 100) {
+                    0..=4 => '#',
+                    5..=29 => 'O',
+                    _ => '.',
+                }
+            };
+            out.push(ch);
+        }
+        out.push('\n');
+    }
+
+    out.push('\n');
+    for _ in 0..20 * scale {
+        let line: String = (0..1000).map(|_| rng.pick(&['<', '>', '^', 'v'])).collect();
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Some(out)
+}
+
+// None means the day can't produce a meaningful input at that scale
+pub type Generator = fn(&mut Rng, u32) -> Option<String>;
+
+pub fn for_day(day: u32) -> Option<Generator> {
+    let generator: Generator = match day {
+        1 => day_1,
+        2 => day_2,
+        3 => day_3,
+        4 => day_4,
+        5 => day_5,
+        6 => day_6,
+        7 => day_7,
+        8 => day_8,
+        9 => day_9,
+        10 => day_10,
+        11 => day_11,
+        12 => day_12,
+        13 => day_13,
+        14 => day_14,
+        15 => day_15,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+// Same input every run for a given day and scale
+pub fn generate(day: u32, scale: u32) -> Option<String> {
+    let generator = for_day(day)?;
+    let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15 ^ ((day as u64) << 32 | scale as u64));
+    generator(&mut rng, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every part should have something to find at scale 1, or stress runs
+    // only ever time the path that rejects everything
+    #[test]
+    fn answers_are_not_all_zero() {
+        let day_5 = generate(5, 1).unwrap();
+        assert_ne!(day_5::part_1(&day_5), 0);
+        assert_ne!(day_5::part_2(&day_5), 0);
+
+        let day_13 = generate(13, 1).unwrap();
+        assert_ne!(day_13::part_1(&day_13), 0);
+        assert_ne!(day_13::part_2(&day_13), 0);
+    }
+}
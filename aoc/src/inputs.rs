@@ -0,0 +1,18 @@
+use std::fs;
+use std::io;
+
+use crate::layout::Layout;
+
+// (year, day, kind, contents) for every input baked in by the embed-inputs feature
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+pub fn load(layout: &Layout, day: u32, kind: &str) -> io::Result<String> {
+    let embedded = EMBEDDED
+        .iter()
+        .find(|&&(y, d, k, _)| y == layout.year && d == day && k == kind);
+
+    match embedded {
+        Some(&(_, _, _, contents)) => Ok(contents.to_string()),
+        None => fs::read_to_string(layout.input_path(day, kind)),
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+// 2024 predates per-year folders, so its days and inputs live at the top of the repo
+pub const LEGACY_YEAR: u32 = 2024;
+
+// Where one year's day crates and inputs live:
+//   2024:        Day_N/day_N, input/dayN
+//   other years: <year>/Day_N/day_N, <year>/input/dayN
+pub struct Layout {
+    root: PathBuf,
+    pub year: u32,
+}
+
+impl Layout {
+    pub fn new(root: &Path, year: u32) -> Self {
+        Layout {
+            root: root.to_path_buf(),
+            year,
+        }
+    }
+
+    pub fn year_dir(&self) -> PathBuf {
+        if self.year == LEGACY_YEAR {
+            self.root.clone()
+        } else {
+            self.root.join(self.year.to_string())
+        }
+    }
+
+    pub fn day_dir(&self, day: u32) -> PathBuf {
+        self.year_dir()
+            .join(format!("Day_{}", day))
+            .join(format!("day_{}", day))
+    }
+
+    pub fn input_dir(&self, day: u32) -> PathBuf {
+        self.year_dir().join("input").join(format!("day{}", day))
+    }
+
+    pub fn input_path(&self, day: u32, kind: &str) -> PathBuf {
+        self.input_dir(day).join(format!("{}.txt", kind))
+    }
+
+    // Shared by every year; each plugin says which year and day it solves
+    pub fn plugins_dir(&self) -> PathBuf {
+        self.root.join("plugins")
+    }
+}
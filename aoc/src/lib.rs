@@ -0,0 +1,23 @@
+pub mod alloc;
+pub mod bench;
+pub mod binary;
+pub mod config;
+pub mod ffi;
+pub mod generate;
+pub mod inputs;
+pub mod layout;
+#[cfg(feature = "plugins")]
+pub mod plugins;
+#[cfg(feature = "plugins")]
+pub mod race;
+#[cfg(feature = "tui")]
+pub mod results;
+pub mod run;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod snapshot;
+pub mod solvers;
+pub mod stress;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod watch;
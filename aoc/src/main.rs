@@ -0,0 +1,197 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+use aoc::config::Config;
+use aoc::layout::Layout;
+use aoc::run::Input;
+use aoc::{bench, snapshot, stress, watch};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: aoc::alloc::CountingAlloc = aoc::alloc::CountingAlloc;
+
+fn repo_root() -> PathBuf {
+    // AOC_ROOT lets a copied binary point at a checkout other than the one it was built from
+    match env::var_os("AOC_ROOT") {
+        Some(root) => PathBuf::from(root),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf(),
+    }
+}
+
+fn print_usage() {
+    println!("Usage:");
+    println!("  aoc run [day] [--part <1|2>] [--sample | --input <file>]");
+    println!("  aoc bench [day] [--sample] [--iterations <n>]");
+    println!("  aoc watch <day> [--part <1|2>]");
+    println!("  aoc snapshot [day] [--accept]");
+    println!("  aoc stress [day] [--part <1|2>] [--budget <seconds>] [--max-scale <n>]");
+    println!("  aoc tui (needs --features tui)");
+    println!("  aoc plugins (needs --features plugins)");
+    println!("  aoc race <day> [--impl <name,...>] [--part <1|2>] [--sample | --input <file>] [--iterations <n>]");
+    println!("    (needs --features plugins; run <day> also takes --impl <name>)");
+    println!();
+    println!("Every command takes --year <year>, defaulting to the year in aoc.toml");
+}
+
+fn usage_error() -> ! {
+    print_usage();
+    process::exit(1);
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let pos = args.iter().position(|a| a == flag)?;
+    match args.get(pos + 1) {
+        Some(value) => Some(value),
+        None => usage_error(),
+    }
+}
+
+fn parse_day(arg: Option<&String>) -> u32 {
+    match arg.and_then(|s| s.parse().ok()) {
+        Some(day) if (1..=25).contains(&day) => day,
+        _ => usage_error(),
+    }
+}
+
+fn parse_part(args: &[String]) -> Option<u32> {
+    let part = flag_value(args, "--part")?;
+    match part.parse() {
+        Ok(part) if part == 1 || part == 2 => Some(part),
+        _ => usage_error(),
+    }
+}
+
+fn parse_year(args: &[String], root: &Path) -> u32 {
+    match flag_value(args, "--year").map(|y| y.parse()) {
+        Some(Ok(year)) => year,
+        Some(Err(_)) => usage_error(),
+        None => Config::load(root).year,
+    }
+}
+
+fn parse_input(args: &[String], day: Option<u32>) -> Input<'_> {
+    match flag_value(args, "--input") {
+        Some(file) if day.is_some() => Input::File(Path::new(file)),
+        Some(_) => usage_error(),
+        None if args.iter().any(|a| a == "--sample") => Input::Kind("sample"),
+        None => Input::Kind("full"),
+    }
+}
+
+fn parse_iterations(args: &[String], default: u32) -> u32 {
+    match flag_value(args, "--iterations").map(|n| n.parse()) {
+        Some(Ok(n)) if n > 0 => n,
+        Some(_) => usage_error(),
+        None => default,
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let root = repo_root();
+    let layout = Layout::new(&root, parse_year(&args, &root));
+    #[cfg(feature = "plugins")]
+    let registry = aoc::plugins::Registry::discover(&layout.plugins_dir());
+
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => {
+            let day = match args.get(1) {
+                Some(arg) if !arg.starts_with("--") => Some(parse_day(Some(arg))),
+                _ => None,
+            };
+            let part = parse_part(&args[1..]);
+            let input = parse_input(&args[1..], day);
+
+            // Plugins can stand in for days with no solver, or be picked by name
+            #[cfg(feature = "plugins")]
+            {
+                let name = flag_value(&args[1..], "--impl");
+                if name.is_some() && day.is_none() {
+                    usage_error();
+                }
+                aoc::race::run(&layout, &registry, day, part, input, name);
+            }
+            #[cfg(not(feature = "plugins"))]
+            aoc::run::run(&layout, day, part, input);
+        }
+        Some("bench") => {
+            let day = match args.get(1) {
+                Some(arg) if !arg.starts_with("--") => Some(parse_day(Some(arg))),
+                _ => None,
+            };
+            let kind = if args.iter().any(|a| a == "--sample") { "sample" } else { "full" };
+            let iterations = parse_iterations(&args[1..], 10);
+            bench::bench(&layout, day, kind, iterations);
+        }
+        Some("snapshot") => {
+            let day = match args.get(1) {
+                Some(arg) if !arg.starts_with("--") => Some(parse_day(Some(arg))),
+                _ => None,
+            };
+            let accept = args.iter().any(|a| a == "--accept");
+            if !snapshot::snapshot(&layout, day, accept) {
+                process::exit(1);
+            }
+        }
+        Some("stress") => {
+            let day = match args.get(1) {
+                Some(arg) if !arg.starts_with("--") => Some(parse_day(Some(arg))),
+                _ => None,
+            };
+            let part = parse_part(&args[1..]);
+            let budget = match flag_value(&args[1..], "--budget").map(|s| s.parse()) {
+                Some(Ok(secs)) if secs > 0.0 => Duration::from_secs_f64(secs),
+                Some(_) => usage_error(),
+                None => Duration::from_secs(5),
+            };
+            let max_scale = match flag_value(&args[1..], "--max-scale").map(|n| n.parse()) {
+                Some(Ok(n)) => n,
+                Some(Err(_)) => usage_error(),
+                None => 1000,
+            };
+            stress::stress(&layout, day, part, budget, max_scale);
+        }
+        // Spawned by `stress` for each attempt, not meant to be run by hand
+        Some("stress-case") => {
+            let day = parse_day(args.get(1));
+            let part = match args.get(2).map(|p| p.parse()) {
+                Some(Ok(part)) if part == 1 || part == 2 => part,
+                _ => usage_error(),
+            };
+            let scale = match args.get(3).map(|s| s.parse()) {
+                Some(Ok(scale)) => scale,
+                _ => usage_error(),
+            };
+            stress::run_case(day, part, scale);
+        }
+        Some("watch") => {
+            let day = parse_day(args.get(1));
+            let part = parse_part(&args[2..]);
+            watch::watch(&layout, day, part);
+        }
+        #[cfg(feature = "tui")]
+        Some("tui") => {
+            if let Err(e) = aoc::tui::tui(layout) {
+                println!("Dashboard failed: {}", e);
+                process::exit(1);
+            }
+        }
+        #[cfg(feature = "plugins")]
+        Some("plugins") => aoc::plugins::list(&layout, &registry),
+        #[cfg(feature = "plugins")]
+        Some("race") => {
+            let day = parse_day(args.get(1));
+            let part = parse_part(&args[2..]);
+            let input = parse_input(&args[2..], Some(day));
+            let names = flag_value(&args[2..], "--impl").map(|names| names.split(',').collect());
+            let iterations = parse_iterations(&args[2..], 10);
+            aoc::race::race(&layout, &registry, day, part, input, names, iterations);
+        }
+        _ => usage_error(),
+    }
+}
@@ -0,0 +1,349 @@
+use std::env::consts::DLL_EXTENSION;
+use std::ffi::{c_char, CStr};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use libloading::Library;
+
+use crate::ffi::{
+    AocPluginAbiVersionFn, AocPluginFreeStringFn, AocPluginInfoFn, AocPluginSolveFn, AocStatus,
+    AOC_PART_1, AOC_PART_2, AOC_PLUGIN_ABI_VERSION,
+};
+use crate::layout::Layout;
+use crate::solvers::{self, Solver};
+
+// Name the linked solvers go by when picking implementations
+pub const BUILTIN: &str = "builtin";
+
+pub struct Plugin {
+    pub name: String,
+    pub year: u32,
+    pub day: u32,
+    pub path: PathBuf,
+    parts: u32,
+    solve: AocPluginSolveFn,
+    free_string: AocPluginFreeStringFn,
+    // Keeps the function pointers above valid; only stand-ins built by the
+    // tests have none
+    _library: Option<Library>,
+}
+
+fn check_abi_version(version: u32) -> Result<(), String> {
+    if version == AOC_PLUGIN_ABI_VERSION {
+        Ok(())
+    } else {
+        Err(format!("plugin ABI version {} but the runner expects {}", version, AOC_PLUGIN_ABI_VERSION))
+    }
+}
+
+impl Plugin {
+    unsafe fn load(path: &Path) -> Result<Plugin, String> {
+        let library = Library::new(path).map_err(|e| e.to_string())?;
+
+        let abi_version = *library
+            .get::<AocPluginAbiVersionFn>(b"aoc_plugin_abi_version")
+            .map_err(|e| e.to_string())?;
+        check_abi_version(abi_version())?;
+
+        let info = *library
+            .get::<AocPluginInfoFn>(b"aoc_plugin_info")
+            .map_err(|e| e.to_string())?;
+        let solve = *library
+            .get::<AocPluginSolveFn>(b"aoc_plugin_solve")
+            .map_err(|e| e.to_string())?;
+        let free_string = *library
+            .get::<AocPluginFreeStringFn>(b"aoc_plugin_free_string")
+            .map_err(|e| e.to_string())?;
+
+        let info = info().as_ref().ok_or("aoc_plugin_info returned null")?;
+        if info.name.is_null() {
+            return Err("plugin has no name".to_string());
+        }
+        Ok(Plugin {
+            name: CStr::from_ptr(info.name).to_string_lossy().into_owned(),
+            year: info.year,
+            day: info.day,
+            path: path.to_path_buf(),
+            parts: info.parts,
+            solve,
+            free_string,
+            _library: Some(library),
+        })
+    }
+
+    pub fn supports(&self, part: u32) -> bool {
+        match part {
+            1 => self.parts & AOC_PART_1 != 0,
+            2 => self.parts & AOC_PART_2 != 0,
+            _ => false,
+        }
+    }
+
+    pub fn try_solve(&self, part: u32, input: &str) -> Result<String, String> {
+        if !self.supports(part) {
+            return Err(format!("{} does not implement part {}", self.name, part));
+        }
+
+        let mut out: *mut c_char = ptr::null_mut();
+        let status = unsafe { (self.solve)(part, input.as_ptr(), input.len(), &mut out) };
+
+        let text = if out.is_null() {
+            String::new()
+        } else {
+            let text = unsafe { CStr::from_ptr(out) }.to_string_lossy().into_owned();
+            unsafe { (self.free_string)(out) };
+            text
+        };
+
+        if status == AocStatus::Ok as i32 {
+            Ok(text)
+        } else if text.is_empty() {
+            Err(format!("failed with status {}", status))
+        } else {
+            Err(text)
+        }
+    }
+}
+
+// Every library in plugins/ that loads, along with why the others were
+// skipped. Discovered once when the runner starts.
+pub struct Registry {
+    pub plugins: Vec<Plugin>,
+    pub rejected: Vec<(PathBuf, String)>,
+}
+
+impl Registry {
+    pub fn discover(dir: &Path) -> Registry {
+        let mut registry = Registry {
+            plugins: Vec::new(),
+            rejected: Vec::new(),
+        };
+
+        let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == DLL_EXTENSION))
+                .collect(),
+            Err(_) => return registry,
+        };
+        paths.sort();
+
+        for path in paths {
+            let loaded = unsafe { Plugin::load(&path) };
+            registry.add(path, loaded);
+        }
+        registry
+    }
+
+    // Keeps a loaded plugin unless its name is taken for that day
+    fn add(&mut self, path: PathBuf, loaded: Result<Plugin, String>) {
+        let plugin = match loaded {
+            Ok(plugin) => plugin,
+            Err(e) => return self.rejected.push((path, e)),
+        };
+        if plugin.name == BUILTIN {
+            let reason = format!("\"{}\" is reserved for the linked solvers", BUILTIN);
+            return self.rejected.push((path, reason));
+        }
+        let duplicate = self
+            .plugins
+            .iter()
+            .any(|p| p.year == plugin.year && p.day == plugin.day && p.name == plugin.name);
+        if duplicate {
+            let reason = format!("another plugin already provides {}", plugin.name);
+            self.rejected.push((path, reason));
+        } else {
+            self.plugins.push(plugin);
+        }
+    }
+
+    // Days with a plugin but neither a linked solver nor a day folder, in order
+    pub fn plugin_only_days(&self, layout: &Layout) -> Vec<u32> {
+        let mut days: Vec<u32> = self
+            .plugins
+            .iter()
+            .filter(|p| p.year == layout.year)
+            .map(|p| p.day)
+            .filter(|&day| solvers::find(layout.year, day).is_none() && !layout.day_dir(day).is_dir())
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+
+    // The linked solver comes first, then plugins in file name order
+    pub fn implementations(&self, year: u32, day: u32) -> Vec<Implementation<'_>> {
+        let builtin = solvers::find(year, day).map(Implementation::Builtin);
+        let plugins = self
+            .plugins
+            .iter()
+            .filter(|p| p.year == year && p.day == day)
+            .map(Implementation::Plugin);
+        builtin.into_iter().chain(plugins).collect()
+    }
+}
+
+pub enum Implementation<'a> {
+    Builtin(&'static Solver),
+    Plugin(&'a Plugin),
+}
+
+impl Implementation<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            Implementation::Builtin(_) => BUILTIN,
+            Implementation::Plugin(plugin) => &plugin.name,
+        }
+    }
+
+    pub fn supports(&self, part: u32) -> bool {
+        match self {
+            Implementation::Builtin(_) => part == 1 || part == 2,
+            Implementation::Plugin(plugin) => plugin.supports(part),
+        }
+    }
+
+    pub fn try_solve(&self, part: u32, input: &str) -> Result<String, String> {
+        match self {
+            Implementation::Builtin(solver) => solver.try_solve(part, input),
+            Implementation::Plugin(plugin) => plugin.try_solve(part, input),
+        }
+    }
+}
+
+pub fn report_rejected(registry: &Registry) {
+    for (path, reason) in &registry.rejected {
+        println!("skipped {}: {}", path.display(), reason);
+    }
+}
+
+pub fn list(layout: &Layout, registry: &Registry) {
+    if registry.plugins.is_empty() && registry.rejected.is_empty() {
+        println!("No plugins in {}", layout.plugins_dir().display());
+        return;
+    }
+
+    for plugin in &registry.plugins {
+        let parts: Vec<String> = [1, 2]
+            .into_iter()
+            .filter(|&part| plugin.supports(part))
+            .map(|part| part.to_string())
+            .collect();
+        println!(
+            "{} day {:<2} {:<16} parts {:<4} {}",
+            plugin.year,
+            plugin.day,
+            plugin.name,
+            parts.join(","),
+            plugin.path.display()
+        );
+    }
+    report_rejected(registry);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    // Stand-ins for a plugin's entry points, answering the day 1 sample
+
+    unsafe fn reply(status: AocStatus, text: &str, out: *mut *mut c_char) -> i32 {
+        *out = CString::new(text).unwrap().into_raw();
+        status as i32
+    }
+
+    pub(crate) unsafe extern "C" fn right(part: u32, _: *const u8, _: usize, out: *mut *mut c_char) -> i32 {
+        reply(AocStatus::Ok, if part == 1 { "11" } else { "31" }, out)
+    }
+
+    pub(crate) unsafe extern "C" fn wrong(_: u32, _: *const u8, _: usize, out: *mut *mut c_char) -> i32 {
+        reply(AocStatus::Ok, "12", out)
+    }
+
+    pub(crate) unsafe extern "C" fn failing(_: u32, _: *const u8, _: usize, out: *mut *mut c_char) -> i32 {
+        reply(AocStatus::SolverFailed, "bad input", out)
+    }
+
+    unsafe extern "C" fn free_string(s: *mut c_char) {
+        drop(CString::from_raw(s));
+    }
+
+    pub(crate) fn stand_in(name: &str, day: u32, parts: u32, solve: AocPluginSolveFn) -> Plugin {
+        Plugin {
+            name: name.to_string(),
+            year: solvers::LINKED_YEAR,
+            day,
+            path: PathBuf::from(format!("{}.{}", name, DLL_EXTENSION)),
+            parts,
+            solve,
+            free_string,
+            _library: None,
+        }
+    }
+
+    fn registry(plugins: Vec<Plugin>) -> Registry {
+        let mut registry = Registry {
+            plugins: Vec::new(),
+            rejected: Vec::new(),
+        };
+        for plugin in plugins {
+            registry.add(plugin.path.clone(), Ok(plugin));
+        }
+        registry
+    }
+
+    #[test]
+    fn checks_the_abi_version() {
+        assert!(check_abi_version(AOC_PLUGIN_ABI_VERSION).is_ok());
+        let error = check_abi_version(AOC_PLUGIN_ABI_VERSION + 1).unwrap_err();
+        assert!(error.contains("runner expects"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_duplicate_and_reserved_names() {
+        let mut registry = registry(vec![
+            stand_in("fast", 1, AOC_PART_1, right),
+            stand_in("fast", 1, AOC_PART_1 | AOC_PART_2, right),
+            stand_in("fast", 2, AOC_PART_1, right),
+            stand_in(BUILTIN, 3, AOC_PART_1, right),
+        ]);
+        registry.add(PathBuf::from("old.so"), Err("plugin ABI version 0".to_string()));
+
+        let kept: Vec<(&str, u32)> = registry.plugins.iter().map(|p| (p.name.as_str(), p.day)).collect();
+        assert_eq!(kept, [("fast", 1), ("fast", 2)]);
+        let reasons: Vec<&str> = registry.rejected.iter().map(|(_, reason)| reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            ["another plugin already provides fast", "\"builtin\" is reserved for the linked solvers", "plugin ABI version 0"]
+        );
+    }
+
+    #[test]
+    fn merges_plugins_with_the_linked_solvers() {
+        let registry = registry(vec![
+            stand_in("fast", 1, AOC_PART_1, right),
+            stand_in("late", 16, AOC_PART_1 | AOC_PART_2, right),
+            stand_in("later", 16, AOC_PART_2, wrong),
+        ]);
+
+        let names = |day| registry.implementations(solvers::LINKED_YEAR, day).iter().map(|i| i.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(names(1), [BUILTIN, "fast"]);
+        assert_eq!(names(2), [BUILTIN]);
+        assert_eq!(names(16), ["late", "later"]);
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert_eq!(registry.plugin_only_days(&Layout::new(root, solvers::LINKED_YEAR)), [16]);
+    }
+
+    #[test]
+    fn solves_through_the_entry_points() {
+        let plugin = stand_in("fast", 1, AOC_PART_1, right);
+        assert_eq!(plugin.try_solve(1, ""), Ok("11".to_string()));
+        assert_eq!(plugin.try_solve(2, ""), Err("fast does not implement part 2".to_string()));
+        let plugin = stand_in("broken", 1, AOC_PART_1, failing);
+        assert_eq!(plugin.try_solve(1, ""), Err("bad input".to_string()));
+    }
+}
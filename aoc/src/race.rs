@@ -0,0 +1,196 @@
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::inputs;
+use crate::layout::Layout;
+use crate::plugins::{self, Implementation, Registry};
+use crate::run::{self, Input};
+use crate::solvers;
+
+struct Entry<'a> {
+    name: &'a str,
+    outcome: Result<String, String>,
+    min: Duration,
+    mean: Duration,
+}
+
+fn time_implementation<'a>(implementation: &'a Implementation, part: u32, input: &str, iterations: u32) -> Entry<'a> {
+    let start = Instant::now();
+    let outcome = implementation.try_solve(part, input);
+    let mut times = vec![start.elapsed()];
+
+    // A failing implementation isn't worth timing again
+    if outcome.is_ok() {
+        for _ in 1..iterations {
+            let start = Instant::now();
+            let _ = implementation.try_solve(part, input);
+            times.push(start.elapsed());
+        }
+    }
+
+    Entry {
+        name: implementation.name(),
+        outcome,
+        min: *times.iter().min().unwrap(),
+        mean: times.iter().sum::<Duration>() / times.len() as u32,
+    }
+}
+
+// Every implementation that has the part, timed, fastest first and failures last
+fn standings<'a>(implementations: &'a [Implementation], part: u32, input: &str, iterations: u32) -> Vec<Entry<'a>> {
+    let mut entries: Vec<Entry> = implementations
+        .iter()
+        .filter(|i| i.supports(part))
+        .map(|i| time_implementation(i, part, input, iterations))
+        .collect();
+    entries.sort_by_key(|e| (e.outcome.is_err(), e.min));
+    entries
+}
+
+fn disagree(entries: &[Entry]) -> bool {
+    let mut answers: Vec<&String> = entries.iter().filter_map(|e| e.outcome.as_ref().ok()).collect();
+    answers.sort();
+    answers.dedup();
+    answers.len() > 1
+}
+
+// `aoc run` with plugins loaded: the named implementation if there is one,
+// otherwise the linked solver or day binary, and for a day that only a plugin
+// provides, the first plugin for it
+pub fn run(layout: &Layout, registry: &Registry, day: Option<u32>, part: Option<u32>, input: Input, name: Option<&str>) {
+    if let Some(name) = name {
+        let Some(day) = day else {
+            println!("--impl needs a day");
+            return;
+        };
+        return race(layout, registry, day, part, input, Some(vec![name]), 1);
+    }
+
+    plugins::report_rejected(registry);
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let plugin_only = registry.plugin_only_days(layout);
+    let run_plugin = |day: u32| {
+        let implementations = registry.implementations(layout.year, day);
+        let plugin = &implementations[0];
+        println!("Using plugin {}", plugin.name());
+        run::run_with(layout, day, &parts, &input, |part, contents| plugin.try_solve(part, contents));
+    };
+
+    match day {
+        Some(day) if plugin_only.contains(&day) => run_plugin(day),
+        Some(day) => run::run(layout, Some(day), part, input),
+        None => {
+            // The linked solvers (or day binaries) first, then the days only plugins cover
+            if !solvers::for_year(layout.year).is_empty() || (1..=25).any(|day| layout.day_dir(day).is_dir()) {
+                run::run(layout, None, part, input);
+            }
+            plugin_only.into_iter().for_each(run_plugin);
+        }
+    }
+}
+
+// Runs every selected implementation of a day on the same input, fastest first
+pub fn race(
+    layout: &Layout,
+    registry: &Registry,
+    day: u32,
+    part: Option<u32>,
+    input: Input,
+    names: Option<Vec<&str>>,
+    iterations: u32,
+) {
+    plugins::report_rejected(registry);
+
+    let mut implementations = registry.implementations(layout.year, day);
+    if let Some(names) = &names {
+        for name in names {
+            if !implementations.iter().any(|i| i.name() == *name) {
+                println!("Day {} has no implementation called {}", day, name);
+            }
+        }
+        implementations.retain(|i| names.contains(&i.name()));
+    }
+    if implementations.is_empty() {
+        println!("Day {} has no implementations to run", day);
+        return;
+    }
+
+    let contents = match input {
+        Input::Kind(kind) => inputs::load(layout, day, kind),
+        Input::File(path) => fs::read_to_string(path),
+    };
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(e) => {
+            println!("Day {}: failed to read input: {}", day, e);
+            return;
+        }
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for part in parts {
+        let entries = standings(&implementations, part, &contents, iterations);
+        if entries.is_empty() {
+            continue;
+        }
+
+        println!("Day {} part {}", day, part);
+        for (rank, entry) in entries.iter().enumerate() {
+            match &entry.outcome {
+                Ok(answer) => println!(
+                    "  {}. {:<16} {:<20} min {:>9.2?}  mean {:>9.2?}",
+                    rank + 1,
+                    entry.name,
+                    answer,
+                    entry.min,
+                    entry.mean
+                ),
+                Err(e) => println!("  -  {:<16} failed: {}", entry.name, e),
+            }
+        }
+
+        if disagree(&entries) {
+            println!("  answers disagree");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{AOC_PART_1, AOC_PART_2};
+    use crate::plugins::tests::{failing, right, stand_in, wrong};
+
+    const SAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn ranks_and_flags_disagreement() {
+        let plugins = [
+            stand_in("agrees", 1, AOC_PART_1 | AOC_PART_2, right),
+            stand_in("broken", 1, AOC_PART_1 | AOC_PART_2, failing),
+            stand_in("off-by-one", 1, AOC_PART_1, wrong),
+        ];
+        let mut implementations = vec![Implementation::Builtin(solvers::find(solvers::LINKED_YEAR, 1).unwrap())];
+        implementations.extend(plugins.iter().map(Implementation::Plugin));
+
+        let entries = standings(&implementations, 1, SAMPLE, 3);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[3].name, "broken");
+        assert!(entries[..3].iter().all(|e| e.outcome.is_ok()));
+        assert!(entries.windows(2).take(2).all(|w| w[0].min <= w[1].min));
+        assert!(disagree(&entries));
+
+        // off-by-one has no part 2, and the rest agree on it
+        let entries = standings(&implementations, 2, SAMPLE, 1);
+        assert_eq!(entries.len(), 3);
+        assert!(!disagree(&entries));
+        assert_eq!(entries[0].outcome.as_deref(), Ok("31"));
+    }
+}
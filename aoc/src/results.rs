@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Anything slower than this is flagged on the dashboard
+pub const SLOW_THRESHOLD: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub outcome: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+impl RunRecord {
+    pub fn is_slow(&self) -> bool {
+        self.elapsed > SLOW_THRESHOLD
+    }
+}
+
+// Each year keeps its own results next to its day folders
+fn results_path(year_dir: &Path) -> PathBuf {
+    year_dir.join(".aoc").join("results.tsv")
+}
+
+// Tabs and newlines would break the one-record-per-line format
+fn clean(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+pub fn load(year_dir: &Path) -> Vec<RunRecord> {
+    let contents = match fs::read_to_string(results_path(year_dir)) {
+        Ok(contents) => contents,
+        Err(_) => return Vec::new(),
+    };
+
+    // day, part, input, ok/err, answer or error, elapsed in microseconds
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 6 {
+                return None;
+            }
+
+            let outcome = match fields[3] {
+                "ok" => Ok(fields[4].to_string()),
+                _ => Err(fields[4].to_string()),
+            };
+
+            Some(RunRecord {
+                day: fields[0].parse().ok()?,
+                part: fields[1].parse().ok()?,
+                input: fields[2].to_string(),
+                outcome,
+                elapsed: Duration::from_micros(fields[5].parse().ok()?),
+            })
+        })
+        .collect()
+}
+
+pub fn save(year_dir: &Path, records: &[RunRecord]) {
+    let path = results_path(year_dir);
+    let contents: String = records
+        .iter()
+        .map(|record| {
+            let (status, text) = match &record.outcome {
+                Ok(answer) => ("ok", answer),
+                Err(e) => ("err", e),
+            };
+            format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                record.day,
+                record.part,
+                clean(&record.input),
+                status,
+                clean(text),
+                record.elapsed.as_micros()
+            )
+        })
+        .collect();
+
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(path, contents);
+}
+
+// Keeps only the latest record for each day and part
+pub fn update(records: &mut Vec<RunRecord>, record: RunRecord) {
+    records.retain(|r| r.day != record.day || r.part != record.part);
+    records.push(record);
+    records.sort_by_key(|r| (r.day, r.part));
+}
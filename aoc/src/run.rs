@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::binary;
+use crate::inputs;
+use crate::layout::Layout;
+use crate::solvers::{self, Solver};
+
+#[derive(Clone, Copy)]
+pub enum Input<'a> {
+    Kind(&'a str),
+    File(&'a Path),
+}
+
+fn run_solver(layout: &Layout, solver: &Solver, parts: &[u32], input: &Input) {
+    run_with(layout, solver.day, parts, input, |part, contents| solver.try_solve(part, contents));
+}
+
+// Runs and times each part of a day with any implementation, e.g. a plugin
+pub fn run_with(
+    layout: &Layout,
+    day: u32,
+    parts: &[u32],
+    input: &Input,
+    try_solve: impl Fn(u32, &str) -> Result<String, String>,
+) {
+    let contents = match input {
+        Input::Kind(kind) => inputs::load(layout, day, kind),
+        Input::File(path) => fs::read_to_string(path),
+    };
+
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(e) => {
+            println!("Day {}: failed to read input: {}", day, e);
+            return;
+        }
+    };
+
+    println!("Day {}", day);
+    for &part in parts {
+        let start = Instant::now();
+        match try_solve(part, &contents) {
+            Ok(answer) => println!("  Part {}: {} ({:.2?})", part, answer, start.elapsed()),
+            Err(e) => println!("  Part {}: failed: {}", part, e),
+        }
+    }
+}
+
+// Years that aren't linked into the runner are built and run as separate binaries
+fn run_binary(layout: &Layout, day: u32, parts: &[u32], input: &Input) {
+    let day_dir = layout.day_dir(day);
+    let input_file = match input {
+        Input::Kind(kind) => layout.input_path(day, kind),
+        Input::File(path) => path.to_path_buf(),
+    };
+
+    if !binary::build(&day_dir) {
+        println!("Day {}: build failed", day);
+        return;
+    }
+
+    let start = Instant::now();
+    let answers = binary::run_day(&day_dir, day, &input_file);
+    let elapsed = start.elapsed();
+
+    println!("Day {} ({:.2?} for both parts)", day, elapsed);
+    for part in parts {
+        match answers.get(part) {
+            Some(answer) => println!("  Part {}: {}", part, answer),
+            None => println!("  Part {}: no answer printed", part),
+        }
+    }
+}
+
+pub fn run(layout: &Layout, day: Option<u32>, part: Option<u32>, input: Input) {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if solvers::for_year(layout.year).is_empty() {
+        match day {
+            Some(day) => run_binary(layout, day, &parts, &input),
+            None => {
+                for day in (1..=25).filter(|&day| layout.day_dir(day).is_dir()) {
+                    run_binary(layout, day, &parts, &input);
+                }
+            }
+        }
+        return;
+    }
+
+    match day {
+        Some(day) => match solvers::find(layout.year, day) {
+            Some(solver) => run_solver(layout, solver, &parts, &input),
+            None => println!("Day {} has no solver", day),
+        },
+        None => {
+            for solver in solvers::for_year(layout.year) {
+                run_solver(layout, solver, &parts, &input);
+            }
+        }
+    }
+}
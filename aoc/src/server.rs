@@ -0,0 +1,505 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::solvers::{self, Solver};
+
+// Puzzle inputs are tens of KiB; anything this large is a mistake
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub port: u16,
+    // Solver runs allowed at once; extra requests get a 503
+    pub max_concurrent: usize,
+    // Solver threads that outlived their request's timeout and are still
+    // running. A solver thread can't be stopped, so past this many new
+    // requests get a 503 rather than piling up more threads.
+    pub max_detached: usize,
+    // Connections handled at once, counting ones still sending their request;
+    // more wait to be accepted
+    pub max_connections: usize,
+    pub timeout: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        let max_concurrent = thread::available_parallelism().map_or(4, |n| n.get());
+        ServerConfig {
+            port: 3030,
+            max_concurrent,
+            max_detached: max_concurrent,
+            max_connections: 64,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response::new(status, json!({ "error": message.into() }))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+// One unit of a counted limit, given back when dropped. A request holds one
+// for its solve until it answers, timed out or not, while the solver thread
+// holds one on the thread count until the solver really finishes.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn acquire(count: &Arc<AtomicUsize>, max: usize) -> Option<Slot> {
+        let mut current = count.load(Ordering::SeqCst);
+        loop {
+            if current >= max {
+                return None;
+            }
+            match count.compare_exchange(current, current + 1, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return Some(Slot(Arc::clone(count))),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Matches /day/{n}/part/{p}
+fn parse_route(path: &str) -> Option<(u32, u32)> {
+    let rest = path.strip_prefix("/day/")?;
+    let (day, part) = rest.split_once("/part/")?;
+    Some((day.parse().ok()?, part.parse().ok()?))
+}
+
+// The shared counters behind the limits in ServerConfig
+#[derive(Clone)]
+struct Limits {
+    config: Arc<ServerConfig>,
+    // Requests waiting on a solver
+    in_flight: Arc<AtomicUsize>,
+    // Solver threads alive, including detached ones
+    threads: Arc<AtomicUsize>,
+    connections: Arc<AtomicUsize>,
+    find: fn(u32) -> Option<&'static Solver>,
+}
+
+fn solve(limits: &Limits, method: &str, path: &str, body: Vec<u8>) -> Response {
+    let config = &limits.config;
+    let Some((day, part)) = parse_route(path) else {
+        return Response::error(404, "expected POST /day/{n}/part/{p}");
+    };
+    if method != "POST" {
+        return Response::error(405, "only POST is supported");
+    }
+    if part != 1 && part != 2 {
+        return Response::error(400, format!("part must be 1 or 2, got {}", part));
+    }
+    let Some(solver) = (limits.find)(day) else {
+        return Response::error(404, format!("no solver for day {}", day));
+    };
+    let Ok(input) = String::from_utf8(body) else {
+        return Response::error(400, "input must be UTF-8");
+    };
+    let Some(_slot) = Slot::acquire(&limits.in_flight, config.max_concurrent) else {
+        return Response::error(503, "too many requests in flight");
+    };
+    let Some(thread_slot) = Slot::acquire(&limits.threads, config.max_concurrent + config.max_detached) else {
+        return Response::error(503, "too many timed-out solves still running");
+    };
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let outcome = solver.try_solve(part, &input);
+        // Given back before answering, so a request made right after sees it free
+        drop(thread_slot);
+        let _ = tx.send((outcome, start.elapsed()));
+    });
+
+    match rx.recv_timeout(config.timeout) {
+        Ok((Ok(answer), elapsed)) => Response::new(200, json!({
+            "day": day,
+            "part": part,
+            "answer": answer,
+            "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+        })),
+        Ok((Err(e), elapsed)) => Response::new(422, json!({
+            "day": day,
+            "part": part,
+            "error": e,
+            "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+        })),
+        Err(_) => Response::error(504, format!("timed out after {:?}", config.timeout)),
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<(String, String, Vec<u8>), Response> {
+    let bad_request = |_| Response::error(400, "malformed request");
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).map_err(bad_request)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+
+    let mut content_length = None;
+    let mut chunked = false;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).map_err(bad_request)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                let length = value.trim().parse().map_err(|_| Response::error(400, "bad Content-Length"))?;
+                content_length = Some(length);
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = true;
+            }
+        }
+    }
+
+    // Bodies are only ever read by length; anything else would be taken as
+    // empty and give a wrong answer instead of an error
+    if chunked {
+        return Err(Response::error(411, "Transfer-Encoding isn't supported, send Content-Length"));
+    }
+    let content_length = match content_length {
+        Some(length) => length,
+        None if method == "POST" => return Err(Response::error(411, "Content-Length is required")),
+        None => 0,
+    };
+
+    if content_length > MAX_BODY {
+        return Err(Response::error(413, "input too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    Ok((method.to_string(), path.to_string(), body))
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn handle_connection(mut stream: TcpStream, limits: &Limits) {
+    let _ = stream.set_read_timeout(Some(limits.config.timeout));
+
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader) {
+        Ok((method, path, body)) => solve(limits, &method, &path, body),
+        Err(response) => response,
+    };
+    let _ = write_response(&mut stream, &response);
+}
+
+pub struct Server {
+    listener: TcpListener,
+    limits: Limits,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Server {
+    // Only ever listens on 127.0.0.1; port 0 picks a free port
+    pub fn bind(config: ServerConfig) -> io::Result<Server> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, config.port))?;
+        Ok(Server {
+            listener,
+            limits: Limits {
+                config: Arc::new(config),
+                in_flight: Arc::new(AtomicUsize::new(0)),
+                threads: Arc::new(AtomicUsize::new(0)),
+                connections: Arc::new(AtomicUsize::new(0)),
+                find: |day| solvers::find(solvers::LINKED_YEAR, day),
+            },
+            shutdown: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    // Serves these solvers instead of the linked ones
+    #[cfg(test)]
+    fn with_solvers(mut self, find: fn(u32) -> Option<&'static Solver>) -> Self {
+        self.limits.find = find;
+        self
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.listener.local_addr().unwrap()
+    }
+
+    pub fn run(self) {
+        loop {
+            // At the connection limit, stop accepting until one closes; new
+            // connections wait in the listen backlog rather than each taking a thread
+            let slot = loop {
+                if self.shutdown.load(Ordering::SeqCst) {
+                    return;
+                }
+                match Slot::acquire(&self.limits.connections, self.limits.config.max_connections) {
+                    Some(slot) => break slot,
+                    None => thread::sleep(Duration::from_millis(5)),
+                }
+            };
+            let stream = self.listener.accept();
+            if self.shutdown.load(Ordering::SeqCst) {
+                return;
+            }
+            let Ok((stream, _)) = stream else {
+                continue;
+            };
+
+            let limits = self.limits.clone();
+            thread::spawn(move || {
+                let _slot = slot;
+                handle_connection(stream, &limits);
+            });
+        }
+    }
+
+    // Runs the accept loop on a background thread, e.g. for an in-process client
+    pub fn spawn(self) -> ServerHandle {
+        let addr = self.local_addr();
+        let shutdown = Arc::clone(&self.shutdown);
+        let thread = thread::spawn(move || self.run());
+        ServerHandle { addr, shutdown, thread }
+    }
+}
+
+pub struct ServerHandle {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl ServerHandle {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn shutdown(self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+        let _ = self.thread.join();
+    }
+}
+
+// Minimal client for talking to a running server without any HTTP crate
+pub fn post(addr: SocketAddr, path: &str, body: &str) -> io::Result<Response> {
+    let mut stream = TcpStream::connect(addr)?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        addr,
+        body.len(),
+        body
+    )?;
+
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw)?;
+
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed response");
+    let (head, body) = raw.split_once("\r\n\r\n").ok_or_else(invalid)?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(invalid)?;
+    let body = serde_json::from_str(body).map_err(|_| invalid())?;
+    Ok(Response { status, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> ServerHandle {
+        let config = ServerConfig {
+            port: 0,
+            ..ServerConfig::default()
+        };
+        Server::bind(config).unwrap().spawn()
+    }
+
+    // Day 1 stands in for a solver that never finishes part 1; day 2's part 1
+    // waits until a test lets it go
+    static STARTED: AtomicBool = AtomicBool::new(false);
+    static RELEASE: AtomicBool = AtomicBool::new(false);
+
+    fn wait_for(flag: &AtomicBool) {
+        while !flag.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    static SLOW: [Solver; 2] = [
+        Solver::new(1, |_| loop { thread::sleep(Duration::from_secs(60)) }, |_| "quick".to_string()),
+        Solver::new(
+            2,
+            |_| {
+                STARTED.store(true, Ordering::SeqCst);
+                wait_for(&RELEASE);
+                "released".to_string()
+            },
+            |_| "quick".to_string(),
+        ),
+    ];
+
+    fn start_slow(config: ServerConfig) -> ServerHandle {
+        let config = ServerConfig { port: 0, ..config };
+        Server::bind(config).unwrap().with_solvers(|day| SLOW.iter().find(|s| s.day == day)).spawn()
+    }
+
+    // Sends a hand-written request and returns the status code
+    fn raw_status(addr: SocketAddr, request: &str) -> u16 {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).unwrap();
+        raw.split_whitespace().nth(1).unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn solves_posted_input() {
+        let server = start();
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        let response = post(server.addr(), "/day/1/part/1", input).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["answer"], "11");
+        assert!(response.body["elapsed_ms"].is_number());
+
+        let response = post(server.addr(), "/day/1/part/2", input).unwrap();
+        assert_eq!(response.body["answer"], "31");
+        server.shutdown();
+    }
+
+    #[test]
+    fn rejects_bad_routes() {
+        let server = start();
+        assert_eq!(post(server.addr(), "/day/1/part/3", "").unwrap().status, 400);
+        assert_eq!(post(server.addr(), "/day/99/part/1", "").unwrap().status, 404);
+        assert_eq!(post(server.addr(), "/nowhere", "").unwrap().status, 404);
+        assert_eq!(raw_status(server.addr(), "GET /day/1/part/1 HTTP/1.1\r\n\r\n"), 405);
+        server.shutdown();
+    }
+
+    #[test]
+    fn requires_a_length() {
+        let server = start();
+        let chunked = "POST /day/1/part/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n3 4\n\r\n0\r\n\r\n";
+        assert_eq!(raw_status(server.addr(), chunked), 411);
+        let missing = "POST /day/1/part/1 HTTP/1.1\r\nConnection: close\r\n\r\n";
+        assert_eq!(raw_status(server.addr(), missing), 411);
+        server.shutdown();
+    }
+
+    #[test]
+    fn times_out_and_frees_the_slot() {
+        let server = start_slow(ServerConfig {
+            max_concurrent: 1,
+            max_detached: 1,
+            timeout: Duration::from_millis(200),
+            ..ServerConfig::default()
+        });
+
+        assert_eq!(post(server.addr(), "/day/1/part/1", "").unwrap().status, 504);
+        // The runaway solver is still going, but the request gave its slot back
+        let response = post(server.addr(), "/day/1/part/2", "").unwrap();
+        assert_eq!((response.status, response.body["answer"].as_str()), (200, Some("quick")));
+
+        // A second runaway fills the room for detached threads
+        assert_eq!(post(server.addr(), "/day/1/part/1", "").unwrap().status, 504);
+        assert_eq!(post(server.addr(), "/day/1/part/2", "").unwrap().status, 503);
+        server.shutdown();
+    }
+
+    #[test]
+    fn limits_concurrent_solves() {
+        let server = start_slow(ServerConfig {
+            max_concurrent: 1,
+            timeout: Duration::from_secs(30),
+            ..ServerConfig::default()
+        });
+        let addr = server.addr();
+
+        let first = thread::spawn(move || post(addr, "/day/2/part/1", "").unwrap());
+        wait_for(&STARTED);
+        assert_eq!(post(addr, "/day/2/part/2", "").unwrap().status, 503);
+
+        RELEASE.store(true, Ordering::SeqCst);
+        let response = first.join().unwrap();
+        assert_eq!((response.status, response.body["answer"].as_str()), (200, Some("released")));
+        assert_eq!(post(addr, "/day/2/part/2", "").unwrap().status, 200);
+        server.shutdown();
+    }
+
+    #[test]
+    fn limits_connections() {
+        let server = start_slow(ServerConfig {
+            max_connections: 1,
+            timeout: Duration::from_secs(30),
+            ..ServerConfig::default()
+        });
+        let addr = server.addr();
+
+        // Holds the only connection by never sending a request, so the next
+        // one isn't served until it closes
+        let idle = TcpStream::connect(addr).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let waiting = thread::spawn(move || tx.send(post(addr, "/day/1/part/2", "").unwrap().status).unwrap());
+        assert!(rx.recv_timeout(Duration::from_millis(300)).is_err());
+
+        drop(idle);
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(200));
+        waiting.join().unwrap();
+        server.shutdown();
+    }
+}
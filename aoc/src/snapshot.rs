@@ -0,0 +1,250 @@
+use std::fs;
+use std::path::Path;
+
+use crate::layout::Layout;
+use crate::solvers;
+
+// Turns an input into rendered text after some number of steps
+type Render = fn(&str, u32) -> String;
+
+struct Renderer {
+    day: u32,
+    name: &'static str,
+    render: Render,
+}
+
+fn day_14_state(input: &str, steps: u32, width: i32, height: i32) -> String {
+    let robots = day_14::parse_input(input);
+    let (grid, _) = day_14::calculate_grid(&robots, steps as i32, width, height);
+    day_14::render_state(&grid, steps as i32)
+}
+
+fn day_15_warehouse(input: &str, steps: u32, wide: bool) -> String {
+    let (map, moves) = day_15::parse_input(input);
+    let mut warehouse = day_15::Warehouse::from_string(map, wide);
+    for movement in moves.chars().take(steps as usize) {
+        let (dr, dc) = day_15::direction(movement);
+        warehouse.try_move(dr, dc);
+    }
+    warehouse.render()
+}
+
+// New renderers get an entry here and can then be used from a day's cases.txt
+static RENDERERS: &[Renderer] = &[
+    Renderer {
+        day: 14,
+        name: "state",
+        render: |input, steps| day_14_state(input, steps, day_14::WIDTH, day_14::HEIGHT),
+    },
+    // The 11x7 room the puzzle's example uses
+    Renderer {
+        day: 14,
+        name: "small-state",
+        render: |input, steps| day_14_state(input, steps, 11, 7),
+    },
+    Renderer {
+        day: 15,
+        name: "warehouse",
+        render: |input, steps| day_15_warehouse(input, steps, false),
+    },
+    Renderer {
+        day: 15,
+        name: "wide-warehouse",
+        render: |input, steps| day_15_warehouse(input, steps, true),
+    },
+];
+
+// One line of snapshots/cases.txt: <name> <renderer> <steps> [input]. The
+// input is read from <input>.input, defaulting to <name>.input, and the
+// expected output is kept in <name>.snap.
+struct Case {
+    name: String,
+    renderer: String,
+    steps: u32,
+    input: String,
+}
+
+fn load_cases(dir: &Path) -> Result<Vec<Case>, String> {
+    let contents = match fs::read_to_string(dir.join("cases.txt")) {
+        Ok(contents) => contents,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [name, renderer, steps, ref input @ ..] if input.len() <= 1 => Ok(Case {
+                name: name.to_string(),
+                renderer: renderer.to_string(),
+                steps: steps.parse().map_err(|_| format!("bad step count in \"{}\"", line))?,
+                input: input.first().unwrap_or(&name).to_string(),
+            }),
+            _ => Err(format!("expected <name> <renderer> <steps> [input], got \"{}\"", line)),
+        })
+        .collect()
+}
+
+enum Op<'a> {
+    Same,
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// Line diff from the longest common subsequence; snapshots are small grids
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(usize, Op<'a>)> {
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((j + 1, Op::Same));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            ops.push((j + 1, Op::Added(new[j])));
+            j += 1;
+        } else {
+            ops.push((i + 1, Op::Removed(old[i])));
+            i += 1;
+        }
+    }
+    ops
+}
+
+fn print_hunk(removed: &mut Vec<(usize, &str)>, added: &mut Vec<(usize, &str)>) {
+    for (line, text) in removed.iter() {
+        println!("    {:>4} - {}", line, text);
+    }
+    for (k, (line, text)) in added.iter().enumerate() {
+        println!("    {:>4} + {}", line, text);
+
+        // Point at the first changed column when a line was edited in place
+        if removed.len() == added.len() {
+            let before = removed[k].1.chars();
+            if let Some(col) = before.zip(text.chars()).position(|(a, b)| a != b) {
+                println!("           {}^", " ".repeat(col));
+            }
+        }
+    }
+    removed.clear();
+    added.clear();
+}
+
+fn print_diff(expected: &str, actual: &str) {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for (line, op) in diff_lines(&old, &new) {
+        match op {
+            Op::Same => print_hunk(&mut removed, &mut added),
+            Op::Removed(text) => removed.push((line, text)),
+            Op::Added(text) => added.push((line, text)),
+        }
+    }
+    print_hunk(&mut removed, &mut added);
+}
+
+// Returns whether every snapshot for the day matched
+fn check_day(layout: &Layout, day: u32, accept: bool) -> bool {
+    let dir = layout.day_dir(day).join("snapshots");
+    let cases = match load_cases(&dir) {
+        Ok(cases) => cases,
+        Err(e) => {
+            println!("Day {}: {}", day, e);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for case in cases {
+        let label = format!("Day {} {}", day, case.name);
+        let Some(renderer) = RENDERERS.iter().find(|r| r.day == day && r.name == case.renderer) else {
+            println!("{}: no renderer called {}", label, case.renderer);
+            ok = false;
+            continue;
+        };
+        let input = match fs::read_to_string(dir.join(format!("{}.input", case.input))) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{}: failed to read input: {}", label, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        let actual = (renderer.render)(&input, case.steps);
+        let snap_path = dir.join(format!("{}.snap", case.name));
+        let expected = fs::read_to_string(&snap_path).ok();
+
+        if expected.as_deref() == Some(actual.as_str()) {
+            println!("{}: ok", label);
+        } else if accept {
+            match fs::write(&snap_path, &actual) {
+                Ok(()) => println!("{}: accepted", label),
+                Err(e) => {
+                    println!("{}: failed to write snapshot: {}", label, e);
+                    ok = false;
+                }
+            }
+        } else {
+            match expected {
+                Some(expected) => {
+                    println!("{}: changed", label);
+                    print_diff(&expected, &actual);
+                }
+                None => println!("{}: no snapshot yet", label),
+            }
+            ok = false;
+        }
+    }
+    ok
+}
+
+pub fn snapshot(layout: &Layout, day: Option<u32>, accept: bool) -> bool {
+    if layout.year != solvers::LINKED_YEAR {
+        println!("Renderers only exist for {}", solvers::LINKED_YEAR);
+        return false;
+    }
+
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => solvers::for_year(layout.year).iter().map(|s| s.day).collect(),
+    };
+
+    let mut ok = true;
+    for day in days {
+        ok &= check_day(layout, day, accept);
+    }
+
+    if !ok && !accept {
+        println!("\nRun with --accept to record the new output");
+    }
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    // The recorded snapshots in the repo match what the renderers draw now
+    #[test]
+    fn snapshots_match() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert!(snapshot(&Layout::new(root, solvers::LINKED_YEAR), None, false));
+    }
+}
@@ -0,0 +1,83 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+pub struct Solver {
+    pub day: u32,
+    part_1: fn(&str) -> String,
+    part_2: fn(&str) -> String,
+}
+
+impl Solver {
+    #[cfg(all(test, feature = "server"))]
+    pub(crate) const fn new(day: u32, part_1: fn(&str) -> String, part_2: fn(&str) -> String) -> Self {
+        Solver { day, part_1, part_2 }
+    }
+
+    pub fn solve(&self, part: u32, input: &str) -> String {
+        match part {
+            1 => (self.part_1)(input),
+            2 => (self.part_2)(input),
+            _ => panic!("Invalid part {}", part),
+        }
+    }
+
+    // Solvers unwrap freely on malformed input, so turn a panic into an error
+    pub fn try_solve(&self, part: u32, input: &str) -> Result<String, String> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.solve(part, input)))
+            .map_err(|payload| panic_message(payload.as_ref()))
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "solver panicked".to_string()
+    }
+}
+
+macro_rules! solver {
+    ($day:literal, $krate:ident) => {
+        Solver {
+            day: $day,
+            part_1: |input| $krate::part_1(input).to_string(),
+            part_2: |input| $krate::part_2(input).to_string(),
+        }
+    };
+}
+
+static SOLVERS: [Solver; 15] = [
+    solver!(1, day_1),
+    solver!(2, day_2),
+    solver!(3, day_3),
+    solver!(4, day_4),
+    solver!(5, day_5),
+    solver!(6, day_6),
+    solver!(7, day_7),
+    solver!(8, day_8),
+    solver!(9, day_9),
+    solver!(10, day_10),
+    solver!(11, day_11),
+    solver!(12, day_12),
+    solver!(13, day_13),
+    solver!(14, day_14),
+    solver!(15, day_15),
+];
+
+// The year whose day crates are linked into the runner
+pub const LINKED_YEAR: u32 = 2024;
+
+// Other years can still be run through their own binaries, just not in-process
+pub fn for_year(year: u32) -> &'static [Solver] {
+    if year == LINKED_YEAR {
+        &SOLVERS
+    } else {
+        &[]
+    }
+}
+
+pub fn find(year: u32, day: u32) -> Option<&'static Solver> {
+    for_year(year).iter().find(|s| s.day == day)
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::generate;
+use crate::layout::Layout;
+use crate::solvers;
+
+// Input sizes tried in turn, as multiples of a normal puzzle input
+const SCALES: [u32; 6] = [1, 10, 30, 100, 300, 1000];
+
+// Generating a 1000x input takes a while but isn't what's being measured
+const GENERATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Solved(Duration),
+    Failed(String),
+    TimedOut,
+    Unsupported,
+}
+
+// Largest scale solved per (day, part) last time, with the budget it ran under
+struct Record {
+    scale: u32,
+    elapsed: Duration,
+    budget: Duration,
+}
+
+fn records_path(year_dir: &Path) -> PathBuf {
+    year_dir.join(".aoc").join("stress.tsv")
+}
+
+fn load_records(year_dir: &Path) -> HashMap<(u32, u32), Record> {
+    let contents = fs::read_to_string(records_path(year_dir)).unwrap_or_default();
+
+    // day, part, scale, elapsed in microseconds, budget in milliseconds
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<u64> = line.split('\t').map(|f| f.parse().ok()).collect::<Option<_>>()?;
+            if fields.len() != 5 {
+                return None;
+            }
+            let record = Record {
+                scale: fields[2] as u32,
+                elapsed: Duration::from_micros(fields[3]),
+                budget: Duration::from_millis(fields[4]),
+            };
+            Some(((fields[0] as u32, fields[1] as u32), record))
+        })
+        .collect()
+}
+
+fn save_records(year_dir: &Path, records: &HashMap<(u32, u32), Record>) {
+    let mut keys: Vec<_> = records.keys().collect();
+    keys.sort();
+
+    let contents: String = keys
+        .into_iter()
+        .map(|key| {
+            let record = &records[key];
+            format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                key.0,
+                key.1,
+                record.scale,
+                record.elapsed.as_micros(),
+                record.budget.as_millis()
+            )
+        })
+        .collect();
+
+    let path = records_path(year_dir);
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(path, contents);
+}
+
+// Each attempt runs in its own process so one that blows the budget can be killed
+fn attempt(day: u32, part: u32, scale: u32, budget: Duration) -> Outcome {
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return Outcome::Failed(e.to_string()),
+    };
+    let child = Command::new(exe)
+        .args(["stress-case", &day.to_string(), &part.to_string(), &scale.to_string()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => return Outcome::Failed(e.to_string()),
+    };
+
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let outcome = match rx.recv_timeout(GENERATION_TIMEOUT).as_deref() {
+        Ok("ready") => match rx.recv_timeout(budget) {
+            Ok(line) => reported(&line),
+            Err(RecvTimeoutError::Timeout) => Outcome::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => Outcome::Failed("solver process died".to_string()),
+        },
+        Ok("unsupported") => Outcome::Unsupported,
+        Ok(_) | Err(_) => Outcome::Failed("input generation failed".to_string()),
+    };
+
+    let _ = child.kill();
+    let _ = child.wait();
+    outcome
+}
+
+// What the child printed after solving: "ok\t<micros>\t<answer>" or "err\t<message>"
+fn reported(line: &str) -> Outcome {
+    match line.split('\t').collect::<Vec<_>>()[..] {
+        ["ok", micros, _] => match micros.parse() {
+            Ok(micros) => Outcome::Solved(Duration::from_micros(micros)),
+            // Not a time to record; count it against the attempt instead
+            Err(_) => Outcome::Failed(format!("unexpected output: {}", line)),
+        },
+        ["err", message] => Outcome::Failed(message.to_string()),
+        _ => Outcome::Failed(format!("unexpected output: {}", line)),
+    }
+}
+
+// Child side of `attempt`: generate the input, then solve it and report
+pub fn run_case(day: u32, part: u32, scale: u32) {
+    let Some(solver) = solvers::find(solvers::LINKED_YEAR, day) else {
+        println!("unsupported");
+        return;
+    };
+    let Some(input) = generate::generate(day, scale) else {
+        println!("unsupported");
+        return;
+    };
+    println!("ready");
+
+    let start = Instant::now();
+    match solver.try_solve(part, &input) {
+        Ok(answer) => println!("ok\t{}\t{}", start.elapsed().as_micros(), answer),
+        Err(e) => println!("err\t{}", e.replace(['\t', '\n'], " ")),
+    }
+}
+
+fn stress_part(day: u32, part: u32, budget: Duration, max_scale: u32) -> (Option<(u32, Duration)>, String) {
+    let mut best = None;
+    let mut stopped = String::new();
+
+    for scale in SCALES.into_iter().filter(|&s| s <= max_scale) {
+        match attempt(day, part, scale, budget) {
+            Outcome::Solved(elapsed) => best = Some((scale, elapsed)),
+            Outcome::Failed(e) => {
+                stopped = format!("{}x failed: {}", scale, e);
+                break;
+            }
+            Outcome::TimedOut => {
+                stopped = format!("{}x timed out", scale);
+                break;
+            }
+            Outcome::Unsupported => {
+                stopped = format!("inputs stop at {}x", best.map_or(0, |(s, _)| s));
+                break;
+            }
+        }
+    }
+    (best, stopped)
+}
+
+fn stress_day(day: u32, parts: &[u32], budget: Duration, max_scale: u32, records: &mut HashMap<(u32, u32), Record>) {
+    println!("Day {}", day);
+    for &part in parts {
+        let (best, stopped) = stress_part(day, part, budget, max_scale);
+
+        let mut line = match best {
+            Some((scale, elapsed)) => format!("  Part {}: {}x in {:.2?}", part, scale, elapsed),
+            None => format!("  Part {}: nothing solved", part),
+        };
+        if !stopped.is_empty() {
+            line.push_str(&format!(" ({})", stopped));
+        }
+
+        if let Some(previous) = records.get(&(day, part)) {
+            if previous.budget == budget {
+                line.push_str(&format!(" (was {}x in {:.2?})", previous.scale, previous.elapsed));
+            } else {
+                line.push_str(&format!(" (was {}x with a {:?} budget)", previous.scale, previous.budget));
+            }
+        }
+        println!("{}", line);
+
+        match best {
+            Some((scale, elapsed)) => {
+                records.insert((day, part), Record { scale, elapsed, budget });
+            }
+            None => {
+                records.remove(&(day, part));
+            }
+        }
+    }
+}
+
+pub fn stress(layout: &Layout, day: Option<u32>, part: Option<u32>, budget: Duration, max_scale: u32) {
+    if layout.year != solvers::LINKED_YEAR {
+        println!("Stress inputs only exist for {}", solvers::LINKED_YEAR);
+        return;
+    }
+
+    if cfg!(debug_assertions) {
+        println!("(debug build: run with --release for representative timings)");
+    }
+    println!("Budget {:?} per run, scales up to {}x", budget, max_scale);
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let year_dir = layout.year_dir();
+    let mut records = load_records(&year_dir);
+
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => solvers::for_year(layout.year).iter().map(|s| s.day).collect(),
+    };
+    for day in days {
+        if generate::for_day(day).is_none() {
+            println!("Day {} has no stress input generator", day);
+            continue;
+        }
+        stress_day(day, &parts, budget, max_scale, &mut records);
+    }
+
+    save_records(&year_dir, &records);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_times_fail_the_attempt() {
+        assert_eq!(reported("ok\t1500\t42"), Outcome::Solved(Duration::from_micros(1500)));
+        assert_eq!(reported("err\tboom"), Outcome::Failed("boom".to_string()));
+        for line in ["ok\t\t42", "ok\t-3\t42", "ok\t1.5ms\t42", "ok\t1500"] {
+            assert_eq!(reported(line), Outcome::Failed(format!("unexpected output: {}", line)));
+        }
+    }
+}
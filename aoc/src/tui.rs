@@ -0,0 +1,504 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io::{self, Stdout};
+use std::panic;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::layout::{self as ui, Constraint};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{Frame, Terminal};
+
+use crate::inputs;
+use crate::layout::Layout;
+use crate::results::{self, RunRecord};
+use crate::solvers::{self, Solver};
+
+type Term = Terminal<CrosstermBackend<Stdout>>;
+
+// Days that ship an interactive visualizer in their binary
+const VISUAL_DAYS: [u32; 2] = [14, 15];
+const LOG_LINES: usize = 200;
+
+#[derive(Clone, Copy, PartialEq)]
+enum InputChoice {
+    Sample,
+    Full,
+    Custom,
+}
+
+enum Status {
+    NotRun,
+    Running,
+    Solved,
+    Slow,
+    Failing,
+}
+
+struct Running {
+    day: u32,
+    part: u32,
+    input: String,
+    started: Instant,
+    rx: Receiver<(Result<String, String>, Duration)>,
+}
+
+struct App {
+    layout: Layout,
+    solvers: &'static [Solver],
+    selected: usize,
+    input: InputChoice,
+    custom_path: String,
+    editing_path: bool,
+    records: Vec<RunRecord>,
+    logs: HashMap<u32, Vec<String>>,
+    queue: VecDeque<(u32, u32)>,
+    running: Option<Running>,
+    // Filled by the panic hook when a solver thread panics
+    last_panic: Arc<Mutex<Option<String>>>,
+}
+
+impl App {
+    fn day(&self) -> u32 {
+        self.solvers[self.selected].day
+    }
+
+    fn log(&mut self, day: u32, line: String) {
+        let lines = self.logs.entry(day).or_default();
+        lines.push(line);
+        if lines.len() > LOG_LINES {
+            lines.remove(0);
+        }
+    }
+
+    fn input_label(&self) -> String {
+        match self.input {
+            InputChoice::Sample => "sample".to_string(),
+            InputChoice::Full => "full".to_string(),
+            InputChoice::Custom => self.custom_path.clone(),
+        }
+    }
+
+    fn input_path(&self, day: u32) -> PathBuf {
+        match self.input {
+            InputChoice::Sample => self.layout.input_path(day, "sample"),
+            InputChoice::Full => self.layout.input_path(day, "full"),
+            InputChoice::Custom => PathBuf::from(&self.custom_path),
+        }
+    }
+
+    fn load_input(&self, day: u32) -> io::Result<String> {
+        match self.input {
+            InputChoice::Sample => inputs::load(&self.layout, day, "sample"),
+            InputChoice::Full => inputs::load(&self.layout, day, "full"),
+            InputChoice::Custom => fs::read_to_string(&self.custom_path),
+        }
+    }
+
+    fn record(&self, day: u32, part: u32) -> Option<&RunRecord> {
+        self.records.iter().find(|r| r.day == day && r.part == part)
+    }
+
+    fn status(&self, day: u32) -> Status {
+        if self.running.as_ref().is_some_and(|r| r.day == day) {
+            return Status::Running;
+        }
+
+        let records: Vec<&RunRecord> = self.records.iter().filter(|r| r.day == day).collect();
+        if records.is_empty() {
+            Status::NotRun
+        } else if records.iter().any(|r| r.outcome.is_err()) {
+            Status::Failing
+        } else if records.iter().any(|r| r.is_slow()) {
+            Status::Slow
+        } else {
+            Status::Solved
+        }
+    }
+
+    // Starts the next queued run. A day whose input can't be read has its
+    // remaining runs dropped, since they'd fail the same way, and the queue
+    // moves on to the next day.
+    fn start_next(&mut self) {
+        if self.running.is_some() {
+            return;
+        }
+        let (day, part, input, contents) = loop {
+            let Some((day, part)) = self.queue.pop_front() else {
+                return;
+            };
+            let input = self.input_label();
+            let error = if self.input == InputChoice::Custom && self.custom_path.is_empty() {
+                "no custom input path set (press e)".to_string()
+            } else {
+                match self.load_input(day) {
+                    Ok(contents) => break (day, part, input, contents),
+                    Err(e) => format!("part {}: failed to read {} input: {}", part, input, e),
+                }
+            };
+            self.log(day, error);
+            self.queue.retain(|&(queued, _)| queued != day);
+        };
+
+        self.log(day, format!("part {}: running on {} ({} bytes)", part, input, contents.len()));
+
+        let (tx, rx) = mpsc::channel();
+        let solver = solvers::find(self.layout.year, day).unwrap();
+        thread::Builder::new()
+            .name("solver".to_string())
+            .spawn(move || {
+                let start = Instant::now();
+                let outcome = solver.try_solve(part, &contents);
+                let _ = tx.send((outcome, start.elapsed()));
+            })
+            .unwrap();
+
+        self.running = Some(Running { day, part, input, started: Instant::now(), rx });
+    }
+
+    fn poll_running(&mut self) {
+        let Some(running) = &self.running else {
+            return;
+        };
+        let Ok((outcome, elapsed)) = running.rx.try_recv() else {
+            return;
+        };
+        let running = self.running.take().unwrap();
+
+        match &outcome {
+            Ok(answer) => {
+                self.log(running.day, format!("part {}: {} in {:.2?}", running.part, answer, elapsed))
+            }
+            Err(e) => {
+                let detail = self.last_panic.lock().unwrap().take().unwrap_or_else(|| e.clone());
+                for line in detail.lines() {
+                    self.log(running.day, format!("part {}: {}", running.part, line));
+                }
+            }
+        }
+        if elapsed > results::SLOW_THRESHOLD {
+            self.log(running.day, format!("part {}: slower than {:?}", running.part, results::SLOW_THRESHOLD));
+        }
+
+        results::update(&mut self.records, RunRecord {
+            day: running.day,
+            part: running.part,
+            input: running.input,
+            outcome,
+            elapsed,
+        });
+        results::save(&self.layout.year_dir(), &self.records);
+        self.start_next();
+    }
+}
+
+fn setup_terminal() -> io::Result<Term> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    Terminal::new(CrosstermBackend::new(io::stdout()))
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+}
+
+fn status_cell(status: &Status) -> Cell<'static> {
+    match status {
+        Status::NotRun => Cell::from("-").fg(Color::DarkGray),
+        Status::Running => Cell::from("running").fg(Color::Cyan),
+        Status::Solved => Cell::from("solved").fg(Color::Green),
+        Status::Slow => Cell::from("slow").fg(Color::Yellow),
+        Status::Failing => Cell::from("failing").fg(Color::Red),
+    }
+}
+
+fn answer_cells(record: Option<&RunRecord>) -> [Cell<'static>; 2] {
+    match record {
+        None => [Cell::from(""), Cell::from("")],
+        Some(record) => {
+            let answer = match &record.outcome {
+                Ok(answer) => Cell::from(answer.clone()),
+                Err(_) => Cell::from("error").fg(Color::Red),
+            };
+            [answer, Cell::from(format!("{:.2?}", record.elapsed))]
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [main, footer] = ui::Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [list, side] = ui::Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(main);
+    let [details, log] = ui::Layout::vertical([Constraint::Length(9), Constraint::Min(0)]).areas(side);
+
+    let rows = app.solvers.iter().map(|solver| {
+        let [p1, t1] = answer_cells(app.record(solver.day, 1));
+        let [p2, t2] = answer_cells(app.record(solver.day, 2));
+        Row::new(vec![
+            Cell::from(format!("{:>3}", solver.day)),
+            status_cell(&app.status(solver.day)),
+            p1,
+            t1,
+            p2,
+            t2,
+        ])
+    });
+    let widths = [
+        Constraint::Length(4),
+        Constraint::Length(8),
+        Constraint::Fill(1),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(Row::new(["Day", "Status", "Part 1", "Time", "Part 2", "Time"]).bold())
+        .block(Block::bordered().title(format!(" Advent of Code {} ", app.layout.year)))
+        .row_highlight_style(Style::new().reversed());
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, list, &mut state);
+
+    let day = app.day();
+    let input_line = match app.input {
+        InputChoice::Custom if app.custom_path.is_empty() => "custom (press e to set a path)".to_string(),
+        InputChoice::Custom => format!("custom: {}", app.custom_path),
+        _ => format!("{} ({})", app.input_label(), app.input_path(day).display()),
+    };
+    let mut lines = vec![
+        Line::from(format!("Input: {}", input_line)),
+        Line::from(""),
+    ];
+    for part in [1, 2] {
+        let text = match app.record(day, part) {
+            None => "not run".to_string(),
+            Some(record) => match &record.outcome {
+                Ok(answer) => format!("{} in {:.2?} on {}", answer, record.elapsed, record.input),
+                Err(e) => format!("failed on {}: {}", record.input, e),
+            },
+        };
+        lines.push(Line::from(format!("Part {}: {}", part, text)));
+    }
+    if let Some(running) = &app.running {
+        lines.push(Line::from(format!(
+            "Running day {} part {} for {:.1?}",
+            running.day,
+            running.part,
+            running.started.elapsed()
+        )).fg(Color::Cyan));
+    }
+    if VISUAL_DAYS.contains(&day) {
+        lines.push(Line::from("Visualizer available (v / V)").fg(Color::DarkGray));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(format!(" Day {} ", day))),
+        details,
+    );
+
+    let log_lines: Vec<Line> = app
+        .logs
+        .get(&day)
+        .map(|lines| lines.iter().map(|l| Line::from(l.as_str())).collect())
+        .unwrap_or_default();
+    let visible = log.height.saturating_sub(2) as usize;
+    let scroll = log_lines.len().saturating_sub(visible) as u16;
+    frame.render_widget(
+        Paragraph::new(log_lines)
+            .scroll((scroll, 0))
+            .block(Block::bordered().title(" Run log ")),
+        log,
+    );
+
+    let help = if app.editing_path {
+        format!("Custom input path: {}_  (Enter to confirm, Esc to cancel)", app.custom_path)
+    } else {
+        "up/down select  i input  e custom path  1/2 run part  r run both  v/V visualize  q quit".to_string()
+    };
+    frame.render_widget(Paragraph::new(help).reversed(), footer);
+}
+
+fn launch_visualizer(terminal: &mut Term, app: &mut App, part: u32) -> io::Result<()> {
+    let day = app.day();
+    if !VISUAL_DAYS.contains(&day) {
+        return Ok(());
+    }
+
+    let path = app.input_path(day);
+    if !path.is_file() {
+        app.log(day, format!("visualizer needs an input file, {} not found", path.display()));
+        return Ok(());
+    }
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--release", "--quiet", "--"])
+        .arg(&path)
+        .current_dir(app.layout.day_dir(day));
+    if day == 15 {
+        command.args(["--visual", &part.to_string()]);
+    }
+
+    restore_terminal();
+    let status = command.status();
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    match status {
+        Ok(status) => app.log(day, format!("visualizer exited with {}", status)),
+        Err(e) => app.log(day, format!("failed to launch visualizer: {}", e)),
+    }
+    Ok(())
+}
+
+fn handle_key(terminal: &mut Term, app: &mut App, code: KeyCode) -> io::Result<bool> {
+    if app.editing_path {
+        match code {
+            KeyCode::Enter => {
+                app.editing_path = false;
+                app.input = InputChoice::Custom;
+            }
+            KeyCode::Esc => app.editing_path = false,
+            KeyCode::Backspace => {
+                app.custom_path.pop();
+            }
+            KeyCode::Char(c) => app.custom_path.push(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    match code {
+        KeyCode::Char('q') | KeyCode::Esc => return Ok(true),
+        KeyCode::Up | KeyCode::Char('k') => app.selected = app.selected.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => app.selected = (app.selected + 1).min(app.solvers.len() - 1),
+        KeyCode::Char('i') => {
+            app.input = match app.input {
+                InputChoice::Sample => InputChoice::Full,
+                InputChoice::Full => InputChoice::Custom,
+                InputChoice::Custom => InputChoice::Sample,
+            }
+        }
+        KeyCode::Char('e') => app.editing_path = true,
+        KeyCode::Char(c @ ('1' | '2')) => {
+            let part = c.to_digit(10).unwrap();
+            app.queue.push_back((app.day(), part));
+            app.start_next();
+        }
+        KeyCode::Char('r') => {
+            app.queue.push_back((app.day(), 1));
+            app.queue.push_back((app.day(), 2));
+            app.start_next();
+        }
+        KeyCode::Char('v') => launch_visualizer(terminal, app, 1)?,
+        KeyCode::Char('V') => launch_visualizer(terminal, app, 2)?,
+        _ => {}
+    }
+    Ok(false)
+}
+
+fn event_loop(terminal: &mut Term, app: &mut App) -> io::Result<()> {
+    loop {
+        app.poll_running();
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && handle_key(terminal, app, key.code)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub fn tui(layout: Layout) -> io::Result<()> {
+    let solvers = solvers::for_year(layout.year);
+    if solvers.is_empty() {
+        println!("No {} solvers are linked into aoc", layout.year);
+        return Ok(());
+    }
+
+    let last_panic = Arc::new(Mutex::new(None));
+
+    // Solver panics go to the run log; anything else restores the terminal first
+    let default_hook = panic::take_hook();
+    let hook_panic = Arc::clone(&last_panic);
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("solver") {
+            *hook_panic.lock().unwrap() = Some(info.to_string());
+        } else {
+            restore_terminal();
+            default_hook(info);
+        }
+    }));
+
+    let mut app = App {
+        records: results::load(&layout.year_dir()),
+        layout,
+        solvers,
+        selected: 0,
+        input: InputChoice::Full,
+        custom_path: String::new(),
+        editing_path: false,
+        logs: HashMap::new(),
+        queue: VecDeque::new(),
+        running: None,
+        last_panic,
+    };
+
+    let mut terminal = setup_terminal()?;
+    let result = event_loop(&mut terminal, &mut app);
+    restore_terminal();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn queued_app(custom_path: &str) -> App {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        App {
+            records: Vec::new(),
+            layout: Layout::new(root, solvers::LINKED_YEAR),
+            solvers: solvers::for_year(solvers::LINKED_YEAR),
+            selected: 0,
+            input: InputChoice::Custom,
+            custom_path: custom_path.to_string(),
+            editing_path: false,
+            logs: HashMap::new(),
+            queue: VecDeque::from([(1, 1), (1, 2), (2, 1), (2, 2)]),
+            running: None,
+            last_panic: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    // A run that can't start mustn't leave the rest of the queue stuck
+    #[test]
+    fn unreadable_input_doesnt_stall_the_queue() {
+        let mut app = queued_app("");
+        app.start_next();
+        assert!(app.running.is_none() && app.queue.is_empty());
+        assert_eq!(app.logs[&1].len(), 1);
+        assert_eq!(app.logs[&2].len(), 1);
+
+        let mut app = queued_app("/nonexistent/input.txt");
+        app.start_next();
+        assert!(app.running.is_none() && app.queue.is_empty());
+        assert!(app.logs[&2][0].starts_with("part 1: failed to read"));
+    }
+}
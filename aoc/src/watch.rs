@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::binary;
+use crate::layout::Layout;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+// Editors often write a file several times per save, so wait for things to settle
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const INPUT_KINDS: [&str; 2] = ["sample", "full"];
+
+type Fingerprint = Vec<(PathBuf, SystemTime, u64)>;
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+fn fingerprint(day_dir: &Path, input_dir: &Path) -> Fingerprint {
+    let mut files = vec![day_dir.join("Cargo.toml")];
+    collect_files(&day_dir.join("src"), &mut files);
+    collect_files(input_dir, &mut files);
+
+    let mut result: Fingerprint = files
+        .into_iter()
+        .filter_map(|path| {
+            let meta = fs::metadata(&path).ok()?;
+            Some((path, meta.modified().ok()?, meta.len()))
+        })
+        .collect();
+    result.sort();
+    result
+}
+
+fn wait_for_change(day_dir: &Path, input_dir: &Path, last: &Fingerprint) -> Fingerprint {
+    // Poll until something changes
+    let mut current = fingerprint(day_dir, input_dir);
+    while current == *last {
+        thread::sleep(POLL_INTERVAL);
+        current = fingerprint(day_dir, input_dir);
+    }
+
+    // Then keep waiting until it stops changing
+    loop {
+        thread::sleep(DEBOUNCE);
+        let next = fingerprint(day_dir, input_dir);
+        if next == current {
+            return current;
+        }
+        current = next;
+    }
+}
+
+pub fn watch(layout: &Layout, day: u32, part: Option<u32>) {
+    let day_dir = layout.day_dir(day);
+    let input_dir = layout.input_dir(day);
+
+    if !day_dir.is_dir() {
+        println!("No crate found at {}", day_dir.display());
+        return;
+    }
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    // Answers from the previous run, keyed by (input kind, part)
+    let mut previous: HashMap<(&str, u32), String> = HashMap::new();
+    let mut last = fingerprint(&day_dir, &input_dir);
+
+    println!("Watching {} day {} (Ctrl-C to stop)", layout.year, day);
+    loop {
+        println!("\n=== Day {} ===", day);
+        let start = Instant::now();
+
+        if binary::build(&day_dir) {
+            for kind in INPUT_KINDS {
+                let input_file = input_dir.join(format!("{}.txt", kind));
+                if !input_file.is_file() {
+                    println!("{}: missing {}", kind, input_file.display());
+                    continue;
+                }
+
+                let answers = binary::run_day(&day_dir, day, &input_file);
+                for &p in &parts {
+                    let answer = match answers.get(&p) {
+                        Some(answer) => answer.clone(),
+                        None => "<no answer>".to_string(),
+                    };
+
+                    let change = match previous.insert((kind, p), answer.clone()) {
+                        None => String::new(),
+                        Some(old) if old == answer => " (unchanged)".to_string(),
+                        Some(old) => format!(" (was {})", old),
+                    };
+                    println!("Part {} ({}): {}{}", p, kind, answer, change);
+                }
+            }
+            println!("Done in {:.2?}", start.elapsed());
+        } else {
+            println!("Build failed");
+        }
+
+        last = wait_for_change(&day_dir, &input_dir, &last);
+    }
+}
@@ -5,29 +5,46 @@ if [ -z "$1" ]; then
   echo "Please provide a number."
   exit 1
 fi
+DAY=$1
+shift
 
-# Parse the --type flag
+# Default the year from aoc.toml, falling back to 2024
+YEAR=$(sed -n 's/^year *= *\([0-9]*\).*/\1/p' aoc.toml 2>/dev/null)
+YEAR=${YEAR:-2024}
+
+# Parse the --type and --year flags
 LANGUAGE=""
-if [ "$2" == "--type" ] && [ -n "$3" ]; then
-  LANGUAGE=$3
+while [ -n "$1" ]; do
+  case "$1" in
+    --type) LANGUAGE=$2; shift 2 ;;
+    --year) YEAR=$2; shift 2 ;;
+    *) echo "Unknown option: $1"; exit 1 ;;
+  esac
+done
+
+# 2024 lives at the top of the repo; later years get their own folder
+BASE="."
+if [ "$YEAR" != "2024" ]; then
+  BASE="$YEAR"
+  mkdir -p "$BASE"
 fi
 
-# Create the Day_<input_number> folder in the current directory
-mkdir "Day_$1"
+# Create the Day_<input_number> folder
+mkdir "$BASE/Day_$DAY"
 
 # Create the day<input_number> folder in the input directory
-mkdir -p "input/day$1"
+mkdir -p "$BASE/input/day$DAY"
 
 # Create full.txt and sample.txt inside the day<input_number> folder
-touch "input/day$1/full.txt" "input/day$1/sample.txt"
+touch "$BASE/input/day$DAY/full.txt" "$BASE/input/day$DAY/sample.txt"
 
 # Set up the folder according to the specified language
 if [ "$LANGUAGE" == "rust" ]; then
-  (cd "Day_$1" && cargo init "day_$1" --vcs none)
+  (cd "$BASE/Day_$DAY" && cargo init "day_$DAY" --vcs none)
 fi
 
-echo "Folders Day_$1 and input/day$1 created successfully."
-echo "Files full.txt and sample.txt created inside input/day$1."
+echo "Folders $BASE/Day_$DAY and $BASE/input/day$DAY created successfully ($YEAR)."
+echo "Files full.txt and sample.txt created inside $BASE/input/day$DAY."
 if [ "$LANGUAGE" == "rust" ]; then
-  echo "Rust project initialized in Day_$1/day_$1."
-fi
\ No newline at end of file
+  echo "Rust project initialized in $BASE/Day_$DAY/day_$DAY."
+fi